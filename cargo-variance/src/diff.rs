@@ -0,0 +1,303 @@
+//! The `diff` command, which compares the variances of two versions of a
+//! crate.
+
+use std::fmt::Write;
+
+use serde_json::{json, Value};
+use type_variance::infer::{Field, ParamVariance, Ty};
+use type_variance::VarianceKind;
+
+use crate::report::Report;
+
+/// A change in the variance of a public parameter.
+pub struct Change<'a> {
+    /// The signature of the type in the new version.
+    pub signature: String,
+    /// The file which defines the type in the new version.
+    pub file: &'a str,
+    pub old: &'a ParamVariance,
+    pub new: &'a ParamVariance,
+    /// The marker fields using the parameter which were added.
+    pub added: Vec<&'a Field>,
+    /// The marker fields using the parameter which were removed.
+    pub removed: Vec<&'a Field>,
+}
+
+impl Change<'_> {
+    /// Whether the change may break users of the type.
+    ///
+    /// Widening the variance only allows more subtyping, and so is compatible.
+    /// Narrowing it, or moving between covariance and contravariance, rejects
+    /// coercions which used to compile.
+    pub fn is_breaking(&self) -> bool {
+        self.old.kind.join(self.new.kind) != self.old.kind
+    }
+
+    fn direction(&self) -> &'static str {
+        if self.new.kind < self.old.kind {
+            "widened"
+        } else if self.old.kind < self.new.kind {
+            "narrowed"
+        } else {
+            "changed"
+        }
+    }
+}
+
+/// A change to the public types or to their parameters, which always breaks
+/// users of the old version.
+pub enum SignatureChange<'a> {
+    /// A public type was removed, or is no longer public.
+    RemovedType {
+        /// The signature of the type in the old version.
+        signature: String,
+        /// The file which defined the type in the old version.
+        file: &'a str,
+    },
+    /// A parameter of a public type was removed.
+    RemovedParam {
+        /// The signature of the type in the new version.
+        signature: String,
+        /// The file which defines the type in the new version.
+        file: &'a str,
+        param: &'a str,
+    },
+    /// A parameter without a default was added to a public type.
+    AddedParam {
+        /// The signature of the type in the new version.
+        signature: String,
+        /// The file which defines the type in the new version.
+        file: &'a str,
+        param: &'a str,
+    },
+}
+
+impl SignatureChange<'_> {
+    fn signature(&self) -> &str {
+        match self {
+            SignatureChange::RemovedType { signature, .. }
+            | SignatureChange::RemovedParam { signature, .. }
+            | SignatureChange::AddedParam { signature, .. } => signature,
+        }
+    }
+
+    fn file(&self) -> &str {
+        match self {
+            SignatureChange::RemovedType { file, .. }
+            | SignatureChange::RemovedParam { file, .. }
+            | SignatureChange::AddedParam { file, .. } => file,
+        }
+    }
+
+    fn param(&self) -> Option<&str> {
+        match self {
+            SignatureChange::RemovedType { .. } => None,
+            SignatureChange::RemovedParam { param, .. }
+            | SignatureChange::AddedParam { param, .. } => Some(param),
+        }
+    }
+
+    fn direction(&self) -> &'static str {
+        match self {
+            SignatureChange::RemovedType { .. } | SignatureChange::RemovedParam { .. } => {
+                "removed"
+            }
+            SignatureChange::AddedParam { .. } => "added",
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            SignatureChange::RemovedType { .. } => "removed or no longer public".to_owned(),
+            SignatureChange::RemovedParam { param, .. } => format!("`{}` removed", param),
+            SignatureChange::AddedParam { param, .. } => {
+                format!("`{}` added without a default", param)
+            }
+        }
+    }
+}
+
+/// Finds the public types which were removed, and the parameters of the
+/// public types in both versions which were removed or added without a
+/// default. Removed types come first, in the order of the old version.
+pub fn signature_changes<'a>(old: &'a Report, new: &'a Report) -> Vec<SignatureChange<'a>> {
+    let mut changes = Vec::new();
+    for entry in old.entries(false) {
+        if !new.entries(false).any(|e| e.def.item.name == entry.def.item.name) {
+            changes.push(SignatureChange::RemovedType {
+                signature: entry.signature(),
+                file: &entry.def.file,
+            });
+        }
+    }
+    for entry in new.entries(false) {
+        let before = match old.entries(false).find(|e| e.def.item.name == entry.def.item.name) {
+            Some(before) => before,
+            None => continue,
+        };
+        let (old_params, new_params) = (&before.def.item.params, &entry.def.item.params);
+        for param in old_params.iter().filter(|p| !new_params.contains(p)) {
+            changes.push(SignatureChange::RemovedParam {
+                signature: entry.signature(),
+                file: &entry.def.file,
+                param,
+            });
+        }
+        for param in new_params.iter().filter(|p| !old_params.contains(p)) {
+            if !entry.def.defaults.contains(param) {
+                changes.push(SignatureChange::AddedParam {
+                    signature: entry.signature(),
+                    file: &entry.def.file,
+                    param,
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Finds the changed parameters of the public types which exist in both
+/// versions, in the order of the new version.
+pub fn changes<'a>(old: &'a Report, new: &'a Report) -> Vec<Change<'a>> {
+    let mut changes = Vec::new();
+    for entry in new.entries(false) {
+        let before = match old.entries(false).find(|e| e.def.item.name == entry.def.item.name) {
+            Some(before) => before,
+            None => continue,
+        };
+        for &param in &entry.params {
+            let old_param = match before.params.iter().find(|p| p.name == param.name).copied() {
+                Some(old_param) => old_param,
+                None => continue,
+            };
+            if old_param.kind == param.kind {
+                continue;
+            }
+            let uses = |markers: &'a [(Field, Vec<String>)]| {
+                markers
+                    .iter()
+                    .map(|(field, _)| field)
+                    .filter(|field| mentions(&field.ty, &param.name))
+                    .collect::<Vec<_>>()
+            };
+            let (old_markers, new_markers) = (uses(&before.def.markers), uses(&entry.def.markers));
+            changes.push(Change {
+                signature: entry.signature(),
+                file: &entry.def.file,
+                old: old_param,
+                new: param,
+                added: new_markers.iter().filter(|f| !old_markers.contains(f)).copied().collect(),
+                removed: old_markers.iter().filter(|f| !new_markers.contains(f)).copied().collect(),
+            });
+        }
+    }
+    changes
+}
+
+// Whether a type uses the parameter or lifetime of the given name.
+fn mentions(ty: &Ty, name: &str) -> bool {
+    let any = |tys: &[Ty]| tys.iter().any(|ty| mentions(ty, name));
+    match ty {
+        Ty::Param(param) | Ty::Lifetime(param) => param == name,
+        Ty::Path { args, .. } => any(args),
+        Ty::Ref { lifetime, ty, .. } => lifetime.as_deref() == Some(name) || mentions(ty, name),
+        Ty::Ptr { ty, .. } => mentions(ty, name),
+        Ty::Fn { args, ret } => any(args) || mentions(ret, name),
+        Ty::Dyn { args, lifetime, .. } => any(args) || lifetime.as_deref() == Some(name),
+        Ty::Tuple(tys) => any(tys),
+        Ty::Array(ty, _) | Ty::Slice(ty) | Ty::Projection(ty, _) => mentions(ty, name),
+    }
+}
+
+/// Renders the changes as text, with the breaking changes first.
+pub fn text(signatures: &[SignatureChange<'_>], changes: &[Change<'_>]) -> String {
+    let mut out = String::new();
+    for change in signatures {
+        writeln!(
+            out,
+            "breaking: {}: {}: {}",
+            change.file(),
+            change.signature(),
+            change.describe(),
+        )
+        .unwrap();
+    }
+    let (breaking, compatible): (Vec<_>, Vec<_>) = changes.iter().partition(|c| c.is_breaking());
+    for change in breaking.iter().chain(&compatible) {
+        writeln!(
+            out,
+            "{}: {}: {}: `{}` {} from {} to {}",
+            if change.is_breaking() { "breaking" } else { "non-breaking" },
+            change.file,
+            change.signature,
+            change.new.name,
+            change.direction(),
+            change.old.kind.name(),
+            change.new.kind.name(),
+        )
+        .unwrap();
+        writeln!(out, "    was {}", change.old).unwrap();
+        writeln!(out, "    now {}", change.new).unwrap();
+        for field in &change.added {
+            writeln!(out, "    added marker field `{}`", field).unwrap();
+        }
+        for field in &change.removed {
+            writeln!(out, "    removed marker field `{}`", field).unwrap();
+        }
+    }
+    if signatures.is_empty() && changes.is_empty() {
+        out.push_str("no variance changes\n");
+    } else {
+        writeln!(
+            out,
+            "{} breaking and {} non-breaking changes",
+            signatures.len() + breaking.len(),
+            compatible.len(),
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Renders the changes as a JSON array, with one object per parameter, and
+/// one per removed type. The changes to the signatures come last.
+pub fn json(signatures: &[SignatureChange<'_>], changes: &[Change<'_>]) -> Value {
+    let fields = |fields: &[&Field]| -> Vec<Value> {
+        fields
+            .iter()
+            .map(|field| json!({ "field": field.name, "type": field.ty.to_string() }))
+            .collect()
+    };
+    let kind = |kind: VarianceKind| json!({ "variance": kind.name(), "symbol": kind.to_string() });
+    Value::Array(
+        changes
+            .iter()
+            .map(|change| {
+                json!({
+                    "type": change.signature,
+                    "file": change.file,
+                    "param": change.new.name,
+                    "breaking": change.is_breaking(),
+                    "direction": change.direction(),
+                    "old": kind(change.old.kind),
+                    "new": kind(change.new.kind),
+                    "explanation": {
+                        "old": change.old.to_string(),
+                        "new": change.new.to_string(),
+                    },
+                    "markers_added": fields(&change.added),
+                    "markers_removed": fields(&change.removed),
+                })
+            })
+            .chain(signatures.iter().map(|change| {
+                json!({
+                    "type": change.signature(),
+                    "file": change.file(),
+                    "param": change.param(),
+                    "breaking": true,
+                    "direction": change.direction(),
+                })
+            }))
+            .collect(),
+    )
+}
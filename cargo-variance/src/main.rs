@@ -0,0 +1,160 @@
+//! A Cargo subcommand which reports the variance of the types in a crate.
+//!
+//! The sources are parsed offline, without building the crate, and the
+//! variance of every type and lifetime parameter is inferred with
+//! [`type_variance::infer`]. Fields which use the markers of `type-variance`
+//! are listed alongside. Two versions of a crate can also be compared, to
+//! find the public parameters whose variance changed.
+//!
+//! [`type_variance::infer`]: https://docs.rs/type-variance/*/type_variance/infer/index.html
+
+mod diff;
+mod report;
+mod source;
+
+use std::env;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::source::Definition;
+
+const USAGE: &str = "\
+Reports the variance of the types in a crate.
+
+Usage: cargo variance report [OPTIONS] [PATH]
+       cargo variance diff [OPTIONS] <OLD> <NEW>
+
+Arguments:
+  [PATH]  The root of the crate [default: .]
+  <OLD>   The old version, as a directory or a git revision of the crate
+  <NEW>   The new version, as a directory or a git revision of the crate
+
+Options:
+  --format <FORMAT>  One of `text` or `json` [default: text]
+  --all              Include types which are not `pub` in the report
+  --path <PATH>      The root of the crate whose git revisions to compare
+                     [default: .]
+  -h, --help         Print this message
+
+The diff exits with status 1 if any change is breaking.
+";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug)]
+struct Options {
+    format: Format,
+    all: bool,
+    path: PathBuf,
+    args: Vec<String>,
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    // Cargo passes the name of the subcommand as the first argument.
+    if args.first().map(String::as_str) == Some("variance") {
+        args.remove(0);
+    }
+    if let Err(err) = run(&args) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    match args.first().map(String::as_str) {
+        Some("report") => {
+            let options = parse_options(&args[1..])?;
+            let root = match options.args.as_slice() {
+                [] => &options.path,
+                [root] => Path::new(root),
+                _ => return Err(format!("expected at most one path\n\n{}", USAGE).into()),
+            };
+            let report = report::Report::new(source::load(root)?)?;
+            match options.format {
+                Format::Text => print!("{}", report::text(&report, options.all)),
+                Format::Json => {
+                    let json = report::json(&report, options.all);
+                    println!("{}", serde_json::to_string_pretty(&json)?);
+                }
+            }
+            Ok(())
+        }
+        Some("diff") => {
+            let options = parse_options(&args[1..])?;
+            let (old, new) = match options.args.as_slice() {
+                [old, new] => (load_version(&options.path, old)?, load_version(&options.path, new)?),
+                _ => return Err(format!("expected two versions to compare\n\n{}", USAGE).into()),
+            };
+            let (old, new) = (report::Report::new(old)?, report::Report::new(new)?);
+            let signatures = diff::signature_changes(&old, &new);
+            let changes = diff::changes(&old, &new);
+            match options.format {
+                Format::Text => print!("{}", diff::text(&signatures, &changes)),
+                Format::Json => {
+                    let json = diff::json(&signatures, &changes);
+                    println!("{}", serde_json::to_string_pretty(&json)?);
+                }
+            }
+            if !signatures.is_empty() || changes.iter().any(diff::Change::is_breaking) {
+                process::exit(1);
+            }
+            Ok(())
+        }
+        Some("-h") | Some("--help") | None => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        Some(other) => Err(format!("unknown command `{}`\n\n{}", other, USAGE).into()),
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, Box<dyn Error>> {
+    let mut options = Options {
+        format: Format::Text,
+        all: false,
+        path: PathBuf::from("."),
+        args: Vec::new(),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                options.format = match args.next().map(String::as_str) {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    _ => return Err("expected `text` or `json` after `--format`".into()),
+                }
+            }
+            "--all" => options.all = true,
+            "--path" => match args.next() {
+                Some(path) => options.path = PathBuf::from(path),
+                None => return Err("expected a path after `--path`".into()),
+            },
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                process::exit(0);
+            }
+            flag if flag.starts_with('-') => {
+                return Err(format!("unknown option `{}`\n\n{}", flag, USAGE).into())
+            }
+            arg => options.args.push(arg.to_owned()),
+        }
+    }
+    Ok(options)
+}
+
+// Loads a version of the crate, which is a directory if one exists with the
+// given name, and otherwise a git revision of the crate at `root`.
+fn load_version(root: &Path, version: &str) -> Result<Vec<Definition>, Box<dyn Error>> {
+    if Path::new(version).is_dir() {
+        source::load(Path::new(version))
+    } else {
+        source::load_git(root, version)
+    }
+}
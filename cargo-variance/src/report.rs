@@ -0,0 +1,145 @@
+//! The `report` command, which lists the variance of every type.
+
+use std::error::Error;
+use std::fmt::Write;
+
+use serde_json::{json, Value};
+use type_variance::infer::{self, Cause, Item, ParamVariance, Solution};
+
+use crate::source::Definition;
+
+/// The solved variances of a crate, along with the definitions they belong to.
+pub struct Report {
+    pub definitions: Vec<Definition>,
+    pub solution: Solution,
+}
+
+impl Report {
+    /// Infers the variances of the given definitions. Where several types
+    /// share a name, only the first is kept.
+    pub fn new(definitions: Vec<Definition>) -> Result<Self, Box<dyn Error>> {
+        let mut kept: Vec<Definition> = Vec::new();
+        for def in definitions {
+            if let Some(first) = kept.iter().find(|d| d.item.name == def.item.name) {
+                eprintln!(
+                    "warning: skipping `{}` in {}, which is also defined in {}",
+                    def.item.name, def.file, first.file,
+                );
+                continue;
+            }
+            kept.push(def);
+        }
+        let items: Vec<Item> = kept.iter().map(|d| d.item.clone()).collect();
+        let solution = infer::infer(&items)?;
+        Ok(Self { definitions: kept, solution })
+    }
+
+    /// Returns the definitions to report on, along with their variances.
+    pub fn entries(&self, all: bool) -> impl Iterator<Item = Entry<'_>> {
+        self.definitions
+            .iter()
+            .zip(self.solution.items())
+            .filter(move |(def, _)| all || def.public)
+            .map(move |(def, var)| Entry {
+                def,
+                params: var
+                    .params
+                    .iter()
+                    .filter(|p| !def.consts.contains(&p.name))
+                    .collect(),
+                solution: &self.solution,
+            })
+    }
+}
+
+/// A single type in the report.
+pub struct Entry<'a> {
+    pub def: &'a Definition,
+    pub params: Vec<&'a ParamVariance>,
+    solution: &'a Solution,
+}
+
+impl Entry<'_> {
+    /// The type as written in its definition, such as `struct List<'a, T>`.
+    pub fn signature(&self) -> String {
+        let params: Vec<String> = self
+            .def
+            .item
+            .params
+            .iter()
+            .map(|p| if self.def.consts.contains(p) { format!("const {}", p) } else { p.clone() })
+            .collect();
+        if params.is_empty() {
+            format!("{} {}", self.def.keyword, self.def.item.name)
+        } else {
+            format!("{} {}<{}>", self.def.keyword, self.def.item.name, params.join(", "))
+        }
+    }
+
+    /// The explanations of the other items which a parameter depends on.
+    pub fn reasons(&self, param: &ParamVariance) -> Vec<String> {
+        let explanation = self.solution.explain(&self.def.item.name, &param.name);
+        explanation.map_or_else(Vec::new, |e| e.lines().skip(1).map(str::to_owned).collect())
+    }
+}
+
+/// Renders the report as text, with one block per type.
+pub fn text(report: &Report, all: bool) -> String {
+    let mut out = String::new();
+    for entry in report.entries(all) {
+        writeln!(out, "{}: {}", entry.def.file, entry.signature()).unwrap();
+        let width = entry.params.iter().map(|p| p.name.len()).max().unwrap_or(0);
+        for param in &entry.params {
+            writeln!(out, "    {:<w$}  {}  {}", param.name, param.kind, param, w = width).unwrap();
+            for reason in entry.reasons(param) {
+                writeln!(out, "    {:<w$}     {}", "", reason.trim_start(), w = width).unwrap();
+            }
+        }
+        for (field, markers) in &entry.def.markers {
+            writeln!(out, "    marker `{}` ({})", field, markers.join(", ")).unwrap();
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Renders the report as a JSON array, with one object per type.
+pub fn json(report: &Report, all: bool) -> Value {
+    let entries = report.entries(all).map(|entry| {
+        let params = entry.params.iter().map(|param| {
+            json!({
+                "name": param.name,
+                "variance": param.kind.name(),
+                "symbol": param.kind.to_string(),
+                "explanation": param.to_string(),
+                "causes": param.causes.iter().map(cause_json).collect::<Vec<_>>(),
+            })
+        });
+        let markers = entry.def.markers.iter().map(|(field, markers)| {
+            json!({
+                "field": field.name,
+                "type": field.ty.to_string(),
+                "markers": markers,
+            })
+        });
+        json!({
+            "name": entry.def.item.name,
+            "kind": entry.def.keyword,
+            "file": entry.def.file,
+            "public": entry.def.public,
+            "params": params.collect::<Vec<_>>(),
+            "markers": markers.collect::<Vec<_>>(),
+        })
+    });
+    Value::Array(entries.collect())
+}
+
+fn cause_json(cause: &Cause) -> Value {
+    json!({
+        "field": cause.field.name,
+        "type": cause.field.ty.to_string(),
+        "symbol": cause.kind.to_string(),
+        "via": cause.via.as_ref().map(|(item, param)| json!({ "item": item, "param": param })),
+        "assumed": cause.assumed,
+    })
+}
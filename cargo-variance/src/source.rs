@@ -0,0 +1,389 @@
+//! Loading of type definitions from a crate's sources, as `infer` items.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::parse::{ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{GenericArgument, GenericParam, Generics, PathArguments, ReturnType};
+use syn::{Token, Type, TypeParamBound, Visibility};
+use type_variance::infer::{Field, Item, Ty};
+
+/// The markers of `type-variance` which are called out in reports.
+const MARKERS: &[&str] = &[
+    "Covariant",
+    "Contravariant",
+    "Invariant",
+    "CovariantLifetime",
+    "ContravariantLifetime",
+    "InvariantLifetime",
+    "Lifetime",
+];
+
+/// A struct, enum, or union found in the sources.
+#[derive(Clone, Debug)]
+pub struct Definition {
+    /// The file which defines the type, relative to the crate root.
+    pub file: String,
+    /// One of `struct`, `enum`, or `union`.
+    pub keyword: &'static str,
+    /// Whether the type is declared `pub`.
+    pub public: bool,
+    /// The type as an `infer` item.
+    pub item: Item,
+    /// The names of the const parameters, which have no variance.
+    pub consts: Vec<String>,
+    /// The names of the parameters which have a default.
+    pub defaults: Vec<String>,
+    /// The fields which use markers, with the markers they use.
+    pub markers: Vec<(Field, Vec<String>)>,
+}
+
+/// Loads the definitions from every `.rs` file under the `src` directory of
+/// the crate at `root`, or under `root` itself if it has no `src` directory.
+pub fn load(root: &Path) -> Result<Vec<Definition>, Box<dyn Error>> {
+    let dir = if root.join("src").is_dir() { root.join("src") } else { root.to_owned() };
+    let mut files = Vec::new();
+    collect_files(&dir, &mut files)?;
+    files.sort();
+
+    let mut definitions = Vec::new();
+    for path in files {
+        let source = fs::read_to_string(&path)?;
+        let name = path.strip_prefix(root).unwrap_or(&path).display().to_string();
+        definitions.extend(parse(&name, &source)?);
+    }
+    Ok(definitions)
+}
+
+/// Loads the definitions like [`load`], but from the given git revision of
+/// the crate at `root` rather than from its working tree.
+///
+/// [`load`]: fn.load.html
+pub fn load_git(root: &Path, rev: &str) -> Result<Vec<Definition>, Box<dyn Error>> {
+    let listing = git(root, &["ls-tree", "-r", "--name-only", rev, "--", "."])?;
+    let mut files: Vec<&str> = listing.lines().filter(|path| path.ends_with(".rs")).collect();
+    if files.iter().any(|path| path.starts_with("src/")) {
+        files.retain(|path| path.starts_with("src/"));
+    }
+    files.sort_unstable();
+
+    let mut definitions = Vec::new();
+    for path in files {
+        let source = git(root, &["show", &format!("{}:./{}", rev, path)])?;
+        definitions.extend(parse(path, &source)?);
+    }
+    Ok(definitions)
+}
+
+// Runs a git command in `root` and returns its output.
+fn git(root: &Path, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let output = Command::new("git").arg("-C").arg(root).args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("`git {}` failed: {}", args.join(" "), stderr.trim()).into());
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if path.extension() == Some("rs".as_ref()) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Parses the definitions in the source of a single file, including those in
+/// inline modules.
+pub fn parse(file: &str, source: &str) -> Result<Vec<Definition>, Box<dyn Error>> {
+    let syntax = syn::parse_file(source).map_err(|err| {
+        let start = err.span().start();
+        format!("{}:{}:{}: {}", file, start.line, start.column + 1, err)
+    })?;
+    let mut definitions = Vec::new();
+    collect_items(file, &syntax.items, &mut definitions);
+    Ok(definitions)
+}
+
+fn collect_items(file: &str, items: &[syn::Item], out: &mut Vec<Definition>) {
+    for item in items {
+        let (keyword, vis, ident, generics, fields) = match item {
+            syn::Item::Struct(item) => {
+                let fields = convert_fields(None, &item.fields);
+                ("struct", &item.vis, &item.ident, &item.generics, fields)
+            }
+            syn::Item::Enum(item) => {
+                let fields = item
+                    .variants
+                    .iter()
+                    .flat_map(|variant| convert_fields(Some(&variant.ident), &variant.fields))
+                    .collect();
+                ("enum", &item.vis, &item.ident, &item.generics, fields)
+            }
+            syn::Item::Union(item) => {
+                let fields = convert_fields(None, &item.fields.named);
+                ("union", &item.vis, &item.ident, &item.generics, fields)
+            }
+            syn::Item::Mod(module) => {
+                // Types which only exist in tests are not part of the crate.
+                let test = module.attrs.iter().any(|attr| {
+                    attr.path().is_ident("cfg")
+                        && attr.parse_args::<syn::Ident>().is_ok_and(|arg| arg == "test")
+                });
+                if let (false, Some((_, items))) = (test, &module.content) {
+                    collect_items(file, items, out);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let (params, consts, defaults) = convert_generics(generics);
+        let fields: Vec<Field> = fields
+            .into_iter()
+            .map(|(name, ty)| Field::new(&name, convert_type(&params, ty)))
+            .collect();
+        let markers = fields
+            .iter()
+            .filter_map(|field| {
+                let mut found = Vec::new();
+                find_markers(&field.ty, &mut found);
+                if found.is_empty() {
+                    None
+                } else {
+                    Some((field.clone(), found))
+                }
+            })
+            .collect();
+        out.push(Definition {
+            file: file.to_owned(),
+            keyword,
+            public: matches!(vis, Visibility::Public(_)),
+            item: Item { name: ident.to_string(), params, fields },
+            consts,
+            defaults,
+            markers,
+        });
+    }
+}
+
+fn convert_fields<'a, I>(variant: Option<&syn::Ident>, fields: I) -> Vec<(String, &'a Type)>
+where
+    I: IntoIterator<Item = &'a syn::Field>,
+{
+    fields
+        .into_iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let name = match &field.ident {
+                Some(ident) => ident.to_string(),
+                None => i.to_string(),
+            };
+            let name = match variant {
+                Some(variant) => format!("{}.{}", variant, name),
+                None => name,
+            };
+            (name, &field.ty)
+        })
+        .collect()
+}
+
+fn convert_generics(generics: &Generics) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let mut params = Vec::new();
+    let mut consts = Vec::new();
+    let mut defaults = Vec::new();
+    for param in &generics.params {
+        match param {
+            GenericParam::Lifetime(def) => params.push(def.lifetime.to_string()),
+            GenericParam::Type(def) => {
+                params.push(def.ident.to_string());
+                if def.default.is_some() {
+                    defaults.push(def.ident.to_string());
+                }
+            }
+            // Const parameters are kept, so that the arguments of other items
+            // still line up with their parameters.
+            GenericParam::Const(def) => {
+                params.push(def.ident.to_string());
+                consts.push(def.ident.to_string());
+                if def.default.is_some() {
+                    defaults.push(def.ident.to_string());
+                }
+            }
+        }
+    }
+    (params, consts, defaults)
+}
+
+/// Converts a type from the sources, given the parameters in scope.
+pub fn convert_type(params: &[String], ty: &Type) -> Ty {
+    let is_param = |ident: &syn::Ident| params.iter().any(|p| ident == p);
+    match ty {
+        Type::Path(path) => {
+            if let Some(qself) = &path.qself {
+                let name = path.path.segments.last().map(|s| s.ident.to_string());
+                return Ty::Projection(
+                    Box::new(convert_type(params, &qself.ty)),
+                    name.unwrap_or_default(),
+                );
+            }
+            let segments = &path.path.segments;
+            let first = &segments[0];
+            if is_param(&first.ident) && first.arguments.is_none() {
+                let param = Ty::Param(first.ident.to_string());
+                return match segments.iter().nth(1) {
+                    Some(assoc) => Ty::Projection(Box::new(param), assoc.ident.to_string()),
+                    None => param,
+                };
+            }
+            let name = segments.iter().map(|s| s.ident.to_string()).collect::<Vec<_>>();
+            let args = segments.last().map_or_else(Vec::new, |s| convert_args(params, &s.arguments));
+            Ty::Path { name: name.join("::"), args }
+        }
+        Type::Reference(reference) => Ty::Ref {
+            lifetime: reference.lifetime.as_ref().map(ToString::to_string),
+            mutable: reference.mutability.is_some(),
+            ty: Box::new(convert_type(params, &reference.elem)),
+        },
+        Type::Ptr(ptr) => Ty::Ptr {
+            mutable: ptr.mutability.is_some(),
+            ty: Box::new(convert_type(params, &ptr.elem)),
+        },
+        Type::BareFn(func) => Ty::Fn {
+            args: func.inputs.iter().map(|arg| convert_type(params, &arg.ty)).collect(),
+            ret: Box::new(convert_return(params, &func.output)),
+        },
+        Type::TraitObject(object) => convert_bounds(params, &object.bounds),
+        Type::Tuple(tuple) => {
+            Ty::Tuple(tuple.elems.iter().map(|elem| convert_type(params, elem)).collect())
+        }
+        Type::Array(array) => Ty::Array(
+            Box::new(convert_type(params, &array.elem)),
+            array.len.to_token_stream().to_string(),
+        ),
+        Type::Slice(slice) => Ty::Slice(Box::new(convert_type(params, &slice.elem))),
+        Type::Paren(paren) => convert_type(params, &paren.elem),
+        Type::Group(group) => convert_type(params, &group.elem),
+        Type::Macro(mac) if mac.mac.path.is_ident("markers") => {
+            convert_markers(params, mac.mac.tokens.clone())
+                .unwrap_or_else(|_| opaque(ty.to_token_stream()))
+        }
+        _ => opaque(ty.to_token_stream()),
+    }
+}
+
+// A type which cannot be analyzed, such as a macro invocation.
+fn opaque(tokens: TokenStream) -> Ty {
+    Ty::Path { name: tokens.to_string(), args: Vec::new() }
+}
+
+fn convert_return(params: &[String], output: &ReturnType) -> Ty {
+    match output {
+        ReturnType::Default => Ty::Tuple(Vec::new()),
+        ReturnType::Type(_, ty) => convert_type(params, ty),
+    }
+}
+
+fn convert_args(params: &[String], arguments: &PathArguments) -> Vec<Ty> {
+    match arguments {
+        PathArguments::None => Vec::new(),
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .map(|arg| match arg {
+                GenericArgument::Lifetime(lifetime) => Ty::Lifetime(lifetime.to_string()),
+                GenericArgument::Type(ty) => convert_type(params, ty),
+                GenericArgument::AssocType(assoc) => convert_type(params, &assoc.ty),
+                other => opaque(other.to_token_stream()),
+            })
+            .collect(),
+        // The `Fn(A, B) -> R` sugar, as `Fn<(A, B), Output = R>`.
+        PathArguments::Parenthesized(args) => std::vec![
+            Ty::Tuple(args.inputs.iter().map(|ty| convert_type(params, ty)).collect()),
+            convert_return(params, &args.output),
+        ],
+    }
+}
+
+fn convert_bounds(params: &[String], bounds: &Punctuated<TypeParamBound, Token![+]>) -> Ty {
+    let mut name = None;
+    let mut args = Vec::new();
+    let mut lifetime = None;
+    for bound in bounds {
+        match bound {
+            TypeParamBound::Trait(bound) => {
+                let path = &bound.path;
+                if name.is_none() {
+                    let segments = path.segments.iter().map(|s| s.ident.to_string());
+                    name = Some(segments.collect::<Vec<_>>().join("::"));
+                }
+                if let Some(last) = path.segments.last() {
+                    args.extend(convert_args(params, &last.arguments));
+                }
+            }
+            TypeParamBound::Lifetime(bound) => lifetime = Some(bound.to_string()),
+            _ => {}
+        }
+    }
+    Ty::Dyn { name: name.unwrap_or_default(), args, lifetime }
+}
+
+// Expands a `markers![+'a, -T]` bundle into the tuple of markers it names.
+fn convert_markers(params: &[String], tokens: TokenStream) -> syn::Result<Ty> {
+    let parser = |input: ParseStream| {
+        let mut markers = Vec::new();
+        while !input.is_empty() {
+            let kind = if input.parse::<Option<Token![+]>>()?.is_some() {
+                "Covariant"
+            } else if input.parse::<Option<Token![-]>>()?.is_some() {
+                "Contravariant"
+            } else {
+                input.parse::<Token![=]>()?;
+                "Invariant"
+            };
+            let marker = if input.peek(syn::Lifetime) {
+                let lifetime: syn::Lifetime = input.parse()?;
+                Ty::path(&format!("{}Lifetime", kind), std::vec![Ty::Lifetime(lifetime.to_string())])
+            } else {
+                let ty: Type = input.parse()?;
+                Ty::path(kind, std::vec![convert_type(params, &ty)])
+            };
+            markers.push(marker);
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(Ty::path("Markers", std::vec![Ty::Tuple(markers)]))
+    };
+    parser.parse2(tokens)
+}
+
+fn find_markers(ty: &Ty, found: &mut Vec<String>) {
+    match ty {
+        Ty::Param(_) | Ty::Lifetime(_) => {}
+        Ty::Path { name, args } => {
+            let last = name.rsplit("::").next().unwrap_or(name);
+            if MARKERS.contains(&last) {
+                found.push(last.to_owned());
+            }
+            args.iter().for_each(|arg| find_markers(arg, found));
+        }
+        Ty::Ref { ty, .. } | Ty::Ptr { ty, .. } => find_markers(ty, found),
+        Ty::Fn { args, ret } => {
+            args.iter().for_each(|arg| find_markers(arg, found));
+            find_markers(ret, found);
+        }
+        Ty::Dyn { args, .. } | Ty::Tuple(args) => {
+            args.iter().for_each(|arg| find_markers(arg, found))
+        }
+        Ty::Array(ty, _) | Ty::Slice(ty) | Ty::Projection(ty, _) => find_markers(ty, found),
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/diff").join(name)
+}
+
+fn diff(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cargo-variance"))
+        .args(["variance", "diff"].iter().chain(args))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn text() {
+    let (old, new) = (fixture("old"), fixture("new"));
+    let output = diff(&[old.to_str().unwrap(), new.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "breaking: src/lib.rs: struct Gone<T>: removed or no longer public\n\
+         breaking: src/lib.rs: struct Hidden<T>: removed or no longer public\n\
+         breaking: src/lib.rs: struct Pair<T>: `U` removed\n\
+         breaking: src/lib.rs: struct Extended<T, U, A>: `U` added without a default\n\
+         breaking: src/lib.rs: struct Handle<T>: `T` narrowed from covariant to invariant\n\
+         \x20   was covariant because of field `marker: Covariant<T>`\n\
+         \x20   now invariant because of field `marker: Invariant<T>`\n\
+         \x20   added marker field `marker: Invariant<T>`\n\
+         \x20   removed marker field `marker: Covariant<T>`\n\
+         breaking: src/lib.rs: struct Sink<T>: `T` changed from contravariant to covariant\n\
+         \x20   was contravariant because of field `write: fn(T)`\n\
+         \x20   now covariant because of field `read: fn() -> T`\n\
+         non-breaking: src/lib.rs: struct Shared<'a, T>: `T` widened from invariant to covariant\n\
+         \x20   was invariant because of field `value: &'a Cell<T>`\n\
+         \x20   now covariant because of field `value: &'a T`\n\
+         6 breaking and 1 non-breaking changes\n",
+    );
+
+    let output = diff(&[old.to_str().unwrap(), old.to_str().unwrap()]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "no variance changes\n");
+}
+
+#[test]
+fn git_revisions() {
+    let repo = std::env::temp_dir().join(format!("cargo-variance-diff-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&repo);
+    fs::create_dir_all(repo.join("src")).unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .arg("-C")
+            .arg(&repo)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+
+    git(&["init", "-q"]);
+    for version in &["old", "new"] {
+        fs::copy(fixture(version).join("src/lib.rs"), repo.join("src/lib.rs")).unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", version]);
+    }
+
+    let output = diff(&["--format", "json", "--path", repo.to_str().unwrap(), "HEAD~1", "HEAD"]);
+    fs::remove_dir_all(&repo).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let changes = json.as_array().unwrap();
+    assert_eq!(changes.len(), 7);
+    assert_eq!(changes[0]["type"], "struct Handle<T>");
+    assert_eq!(changes[0]["breaking"], true);
+    assert_eq!(changes[0]["direction"], "narrowed");
+    assert_eq!(changes[0]["markers_added"][0]["type"], "Invariant<T>");
+    assert_eq!(changes[0]["markers_removed"][0]["type"], "Covariant<T>");
+    assert_eq!(changes[1]["type"], "struct Shared<'a, T>");
+    assert_eq!(changes[1]["breaking"], false);
+    assert_eq!(changes[3]["type"], "struct Gone<T>");
+    assert_eq!(changes[3]["param"], Value::Null);
+    assert_eq!(changes[3]["direction"], "removed");
+    assert_eq!(changes[5]["type"], "struct Pair<T>");
+    assert_eq!(changes[5]["param"], "U");
+    assert_eq!(changes[6]["param"], "U");
+    assert_eq!(changes[6]["direction"], "added");
+    assert_eq!(changes[6]["breaking"], true);
+}
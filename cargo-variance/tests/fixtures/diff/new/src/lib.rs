@@ -0,0 +1,37 @@
+use type_variance::{Covariant, Invariant};
+
+pub struct Handle<T> {
+    raw: *const u8,
+    marker: Invariant<T>,
+}
+
+pub struct Shared<'a, T> {
+    value: &'a T,
+}
+
+pub struct Sink<T> {
+    read: fn() -> T,
+}
+
+pub struct Same<T> {
+    value: Box<T>,
+}
+
+struct Private<T> {
+    marker: Invariant<T>,
+}
+
+struct Hidden<T> {
+    value: Box<T>,
+}
+
+pub struct Pair<T> {
+    first: Box<T>,
+    second: Box<T>,
+}
+
+pub struct Extended<T, U, A = ()> {
+    value: Box<T>,
+    extra: Box<U>,
+    alloc: A,
+}
@@ -0,0 +1,41 @@
+use std::cell::Cell;
+
+use type_variance::Covariant;
+
+pub struct Handle<T> {
+    raw: *const u8,
+    marker: Covariant<T>,
+}
+
+pub struct Shared<'a, T> {
+    value: &'a Cell<T>,
+}
+
+pub struct Sink<T> {
+    write: fn(T),
+}
+
+pub struct Same<T> {
+    value: Box<T>,
+}
+
+struct Private<T> {
+    marker: Covariant<T>,
+}
+
+pub struct Gone<T> {
+    value: Box<T>,
+}
+
+pub struct Hidden<T> {
+    value: Box<T>,
+}
+
+pub struct Pair<T, U> {
+    first: Box<T>,
+    second: Box<U>,
+}
+
+pub struct Extended<T> {
+    value: Box<T>,
+}
@@ -0,0 +1,32 @@
+use std::cell::Cell;
+
+use type_variance::{markers, Contravariant, Covariant, Invariant, Lifetime};
+
+pub struct Producer<T> {
+    id: u32,
+    marker: Covariant<T>,
+}
+
+pub struct Consumer<'a, T> {
+    raw: *const u8,
+    marker: Contravariant<T>,
+    scope: Covariant<Lifetime<'a>>,
+}
+
+pub enum Slot<T, U> {
+    Empty,
+    Shared(Cell<T>),
+    Borrowed(Node<U>),
+}
+
+pub struct Query<'db, Row, Filter> {
+    handle: usize,
+    marker: markers![='db, +Row, -Filter],
+}
+
+pub struct Buffer<T, const N: usize> {
+    items: [T; N],
+    marker: Invariant<T>,
+}
+
+mod list;
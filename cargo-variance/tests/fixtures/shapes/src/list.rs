@@ -0,0 +1,7 @@
+use std::marker::PhantomData;
+
+pub(crate) struct Node<T> {
+    value: *mut T,
+    next: Option<Box<Node<T>>>,
+    _owns: PhantomData<T>,
+}
@@ -0,0 +1,64 @@
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::Value;
+
+fn report(args: &[&str]) -> String {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/shapes");
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-variance"))
+        .args(["variance", "report"].iter().chain(args))
+        .arg(&fixture)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn text() {
+    let text = report(&[]);
+    assert!(text.contains(
+        "src/lib.rs: struct Consumer<'a, T>\n\
+         \x20   'a  +  covariant because of field `scope: Covariant<Lifetime<'a>>`\n\
+         \x20   T   -  contravariant because of field `marker: Contravariant<T>`\n\
+         \x20   marker `marker: Contravariant<T>` (Contravariant)\n\
+         \x20   marker `scope: Covariant<Lifetime<'a>>` (Covariant, Lifetime)\n",
+    ));
+    assert!(text.contains(
+        "src/lib.rs: enum Slot<T, U>\n\
+         \x20   T  =  invariant because of field `Shared.0: Cell<T>`\n\
+         \x20   U  =  invariant because of field `Borrowed.0: Node<U>`\n\
+         \x20         where `T` in `Node` is invariant because of field `value: *mut T`\n",
+    ));
+    assert!(text.contains("src/lib.rs: struct Buffer<T, const N>\n"));
+    assert!(!text.contains("struct Node"));
+    assert!(report(&["--all"]).contains("src/list.rs: struct Node<T>\n"));
+}
+
+#[test]
+fn json() {
+    let json: Value = serde_json::from_str(&report(&["--format", "json"])).unwrap();
+    let query = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["name"] == "Query")
+        .unwrap();
+
+    let variances: Vec<_> = query["params"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|param| (param["name"].as_str().unwrap(), param["variance"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        variances,
+        [("'db", "invariant"), ("Row", "covariant"), ("Filter", "contravariant")],
+    );
+    assert_eq!(query["kind"], "struct");
+    assert_eq!(query["public"], true);
+    assert_eq!(
+        query["markers"][0]["markers"],
+        serde_json::json!(["InvariantLifetime", "Covariant", "Contravariant"]),
+    );
+}
@@ -0,0 +1,257 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_quote, Field, Fields, FieldsNamed, GenericParam, Ident, ItemStruct,
+    Lifetime, Token, Type,
+};
+
+/// The variance requested for a single parameter.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Covariant,
+    Contravariant,
+    Invariant,
+}
+
+impl Kind {
+    fn parse_ident(ident: &Ident) -> syn::Result<Self> {
+        match ident.to_string().as_str() {
+            "covariant" => Ok(Kind::Covariant),
+            "contravariant" => Ok(Kind::Contravariant),
+            "invariant" => Ok(Kind::Invariant),
+            _ => Err(syn::Error::new(
+                ident.span(),
+                "expected one of `covariant`, `contravariant`, or `invariant`",
+            )),
+        }
+    }
+
+    /// The variance of a parameter used both `self`-variantly and
+    /// `other`-variantly.
+    pub(crate) fn join(self, other: Self) -> Self {
+        if self == other { self } else { Kind::Invariant }
+    }
+
+    /// The variance of a parameter used `inner`-variantly within a type which
+    /// is itself used `self`-variantly.
+    pub(crate) fn compose(self, inner: Self) -> Self {
+        match (self, inner) {
+            (Kind::Invariant, _) | (_, Kind::Invariant) => Kind::Invariant,
+            (Kind::Covariant, kind) => kind,
+            (Kind::Contravariant, Kind::Covariant) => Kind::Contravariant,
+            (Kind::Contravariant, Kind::Contravariant) => Kind::Covariant,
+        }
+    }
+
+    pub(crate) fn adverb(self) -> &'static str {
+        match self {
+            Kind::Covariant => "covariantly",
+            Kind::Contravariant => "contravariantly",
+            Kind::Invariant => "invariantly",
+        }
+    }
+
+    pub(crate) fn noun(self) -> &'static str {
+        match self {
+            Kind::Covariant => "covariance",
+            Kind::Contravariant => "contravariance",
+            Kind::Invariant => "invariance",
+        }
+    }
+
+    fn marker(self, param: &GenericParam) -> Option<Type> {
+        Some(match (self, param) {
+            (Kind::Covariant, GenericParam::Type(ty)) => {
+                let ident = &ty.ident;
+                parse_quote!(::type_variance::Covariant<#ident>)
+            }
+            (Kind::Contravariant, GenericParam::Type(ty)) => {
+                let ident = &ty.ident;
+                parse_quote!(::type_variance::Contravariant<#ident>)
+            }
+            (Kind::Invariant, GenericParam::Type(ty)) => {
+                let ident = &ty.ident;
+                parse_quote!(::type_variance::Invariant<#ident>)
+            }
+            (Kind::Covariant, GenericParam::Lifetime(def)) => {
+                let lifetime = &def.lifetime;
+                parse_quote!(::type_variance::CovariantLifetime<#lifetime>)
+            }
+            (Kind::Contravariant, GenericParam::Lifetime(def)) => {
+                let lifetime = &def.lifetime;
+                parse_quote!(::type_variance::ContravariantLifetime<#lifetime>)
+            }
+            (Kind::Invariant, GenericParam::Lifetime(def)) => {
+                let lifetime = &def.lifetime;
+                parse_quote!(::type_variance::InvariantLifetime<#lifetime>)
+            }
+            (_, GenericParam::Const(_)) => return None,
+        })
+    }
+}
+
+/// The name of a generic parameter, as written in the attribute arguments.
+#[derive(PartialEq)]
+enum Param {
+    Type(Ident),
+    Lifetime(Lifetime),
+}
+
+impl Param {
+    fn span(&self) -> Span {
+        match self {
+            Param::Type(ident) => ident.span(),
+            Param::Lifetime(lifetime) => lifetime.apostrophe,
+        }
+    }
+
+    fn matches(&self, generic: &GenericParam) -> bool {
+        match (self, generic) {
+            (Param::Type(ident), GenericParam::Type(ty)) => *ident == ty.ident,
+            (Param::Lifetime(lt), GenericParam::Lifetime(def)) => *lt == def.lifetime,
+            _ => false,
+        }
+    }
+}
+
+enum Arg {
+    Param(Param, Kind),
+    Default(Kind),
+    Field(Ident),
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Lifetime) {
+            let lifetime: Lifetime = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let kind = Kind::parse_ident(&input.parse()?)?;
+            return Ok(Arg::Param(Param::Lifetime(lifetime), kind));
+        }
+
+        // `default` is a keyword, so it must be parsed separately from
+        // ordinary parameter names.
+        if input.peek(Token![default]) {
+            input.parse::<Token![default]>()?;
+            input.parse::<Token![=]>()?;
+            return Ok(Arg::Default(Kind::parse_ident(&input.parse()?)?));
+        }
+
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        if name == "field" {
+            Ok(Arg::Field(input.parse()?))
+        } else {
+            let kind = Kind::parse_ident(&input.parse()?)?;
+            Ok(Arg::Param(Param::Type(name), kind))
+        }
+    }
+}
+
+/// The parsed arguments of the `#[variance(...)]` attribute.
+pub struct Args {
+    params: Vec<(Param, Kind)>,
+    default: Kind,
+    field: Ident,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Args {
+            params: Vec::new(),
+            default: Kind::Invariant,
+            field: Ident::new("_variance", Span::call_site()),
+        };
+        for arg in Punctuated::<Arg, Token![,]>::parse_terminated(input)? {
+            match arg {
+                Arg::Param(param, kind) => args.params.push((param, kind)),
+                Arg::Default(kind) => args.default = kind,
+                Arg::Field(field) => args.field = field,
+            }
+        }
+        Ok(args)
+    }
+}
+
+pub fn expand(args: Args, mut item: ItemStruct) -> syn::Result<TokenStream> {
+    for (i, (param, _)) in args.params.iter().enumerate() {
+        if !item.generics.params.iter().any(|generic| param.matches(generic)) {
+            return Err(syn::Error::new(
+                param.span(),
+                "no generic parameter with this name",
+            ));
+        }
+        if args.params[..i].iter().any(|(prev, _)| prev == param) {
+            return Err(syn::Error::new(
+                param.span(),
+                "variance is specified more than once for this parameter",
+            ));
+        }
+    }
+
+    let mut markers = Vec::new();
+    for generic in &item.generics.params {
+        let kind = match args.params.iter().find(|(param, _)| param.matches(generic)) {
+            Some(&(_, kind)) => kind,
+            None if is_used(&item.fields, generic) => continue,
+            None => args.default,
+        };
+        markers.extend(kind.marker(generic));
+    }
+
+    let ty: Type = match markers.len() {
+        0 => return Ok(item.into_token_stream()),
+        1 => markers.pop().unwrap(),
+        _ => parse_quote!(::type_variance::Covariant<(#(#markers,)*)>),
+    };
+
+    let field_name = &args.field;
+    match &mut item.fields {
+        Fields::Named(fields) => {
+            fields.named.push(Field::parse_named.parse2(quote!(#field_name: #ty))?);
+        }
+        Fields::Unnamed(fields) => {
+            fields.unnamed.push(Field::parse_unnamed.parse2(quote!(#ty))?);
+        }
+        Fields::Unit => {
+            let fields: FieldsNamed = parse_quote!({ #field_name: #ty });
+            item.fields = Fields::Named(fields);
+            item.semi_token = None;
+        }
+    }
+
+    Ok(item.into_token_stream())
+}
+
+/// Determine whether any of the struct's fields mention the given parameter.
+fn is_used(fields: &Fields, generic: &GenericParam) -> bool {
+    fields.iter().any(|field| {
+        let tokens = field.ty.to_token_stream();
+        match generic {
+            GenericParam::Type(ty) => mentions_ident(tokens, &ty.ident),
+            GenericParam::Lifetime(def) => mentions_lifetime(tokens, &def.lifetime.ident),
+            GenericParam::Const(_) => true,
+        }
+    })
+}
+
+fn mentions_ident(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(other) => other == *ident,
+        TokenTree::Group(group) => mentions_ident(group.stream(), ident),
+        _ => false,
+    })
+}
+
+fn mentions_lifetime(tokens: TokenStream, ident: &Ident) -> bool {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    tokens.iter().enumerate().any(|(i, token)| match token {
+        TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+            matches!(tokens.get(i + 1), Some(TokenTree::Ident(other)) if other == ident)
+        }
+        TokenTree::Group(group) => mentions_lifetime(group.stream(), ident),
+        _ => false,
+    })
+}
@@ -0,0 +1,361 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::parse::{ParseStream, Parser};
+use syn::{
+    Data, DeriveInput, Field, GenericArgument, GenericParam, Lifetime, PathArguments, ReturnType,
+    Token, Type, TypeParamBound,
+};
+
+use crate::attr::Kind;
+
+/// A use of a generic parameter within a field.
+struct Use<'a> {
+    param: usize,
+    kind: Kind,
+    /// Whether the use is the argument of a variance marker, which declares
+    /// the variance of the parameter rather than merely implying it.
+    marker: bool,
+    field: &'a Field,
+}
+
+/// Checks that no field of the type overrides the variance declared for a
+/// parameter by a marker in another field.
+pub fn check(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fields: Vec<&Field> = match &input.data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(data) => data.fields.named.iter().collect(),
+    };
+    let params: Vec<&GenericParam> = input.generics.params.iter().collect();
+
+    let mut uses = Vec::new();
+    for &field in &fields {
+        let walker = Walker {
+            params: &params,
+            field,
+        };
+        walker.walk(&field.ty, Kind::Covariant, &mut uses);
+    }
+
+    let mut errors: Option<syn::Error> = None;
+    for (p, param) in params.iter().enumerate() {
+        let declared = match uses.iter().find(|u| u.param == p && u.marker) {
+            Some(declared) => declared,
+            None => continue,
+        };
+        let conflicts = uses.iter().filter(|u| {
+            u.param == p
+                && !std::ptr::eq(u.field, declared.field)
+                && declared.kind.join(u.kind) != declared.kind
+        });
+        for conflict in conflicts {
+            let message = format!(
+                "`{name}` is used {used} by this field, which overrides the {declared} \
+                 declared by {marker} and makes `{ty}` {result} with respect to `{name}`",
+                name = param_name(param),
+                used = conflict.kind.adverb(),
+                declared = declared.kind.noun(),
+                marker = match &declared.field.ident {
+                    Some(ident) => format!("field `{}`", ident),
+                    None => "a marker field".to_owned(),
+                },
+                ty = input.ident,
+                result = match declared.kind.join(conflict.kind) {
+                    Kind::Covariant => "covariant",
+                    Kind::Contravariant => "contravariant",
+                    Kind::Invariant => "invariant",
+                },
+            );
+            let error = syn::Error::new_spanned(conflict.field, message);
+            match &mut errors {
+                Some(errors) => errors.combine(error),
+                None => errors = Some(error),
+            }
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(TokenStream::new()),
+    }
+}
+
+fn param_name(param: &GenericParam) -> String {
+    match param {
+        GenericParam::Type(ty) => ty.ident.to_string(),
+        GenericParam::Lifetime(def) => def.lifetime.to_string(),
+        GenericParam::Const(def) => def.ident.to_string(),
+    }
+}
+
+// Finds the uses of the parameters within the type of a field. Uses within
+// types that are not known to the walker are skipped, since their variance
+// cannot be determined here.
+struct Walker<'a> {
+    params: &'a [&'a GenericParam],
+    field: &'a Field,
+}
+
+impl<'a> Walker<'a> {
+    fn walk(&self, ty: &Type, ambient: Kind, uses: &mut Vec<Use<'a>>) {
+        match ty {
+            Type::Path(path) if path.qself.is_none() => {
+                if let Some(param) = self.type_param(path) {
+                    // An associated type such as `T::Item` may be anything.
+                    let kind = if path.path.segments.len() == 1 {
+                        ambient
+                    } else {
+                        Kind::Invariant
+                    };
+                    self.push(uses, param, kind, false);
+                    return;
+                }
+                let last = match path.path.segments.last() {
+                    Some(last) => last,
+                    None => return,
+                };
+                let args: Vec<&GenericArgument> = match &last.arguments {
+                    PathArguments::AngleBracketed(args) => args.args.iter().collect(),
+                    _ => Vec::new(),
+                };
+                let name = last.ident.to_string();
+                if let Some(kind) = marker_kind(&name) {
+                    if let Some(param) = args.first().and_then(|arg| self.marker_param(arg)) {
+                        self.push(uses, param, ambient.compose(kind), true);
+                        return;
+                    }
+                }
+                let kinds = match builtin(&name) {
+                    Some(kinds) => kinds,
+                    None => return,
+                };
+                for (i, arg) in args.into_iter().enumerate() {
+                    let kind = ambient.compose(*kinds.get(i).unwrap_or(&Kind::Invariant));
+                    match arg {
+                        GenericArgument::Type(ty) => self.walk(ty, kind, uses),
+                        GenericArgument::Lifetime(lifetime) => self.lifetime(lifetime, kind, uses),
+                        _ => {}
+                    }
+                }
+            }
+            Type::Path(path) => {
+                if let Some(qself) = &path.qself {
+                    self.walk(&qself.ty, Kind::Invariant, uses);
+                }
+            }
+            Type::Reference(reference) => {
+                if let Some(lifetime) = &reference.lifetime {
+                    self.lifetime(lifetime, ambient, uses);
+                }
+                let inner = if reference.mutability.is_some() {
+                    Kind::Invariant
+                } else {
+                    Kind::Covariant
+                };
+                self.walk(&reference.elem, ambient.compose(inner), uses);
+            }
+            Type::Ptr(ptr) => {
+                let inner = if ptr.mutability.is_some() {
+                    Kind::Invariant
+                } else {
+                    Kind::Covariant
+                };
+                self.walk(&ptr.elem, ambient.compose(inner), uses);
+            }
+            Type::BareFn(func) => {
+                for arg in &func.inputs {
+                    self.walk(&arg.ty, ambient.compose(Kind::Contravariant), uses);
+                }
+                if let ReturnType::Type(_, ret) = &func.output {
+                    self.walk(ret, ambient, uses);
+                }
+            }
+            Type::TraitObject(object) => {
+                for bound in &object.bounds {
+                    match bound {
+                        TypeParamBound::Trait(bound) => {
+                            let tokens = bound.path.to_token_stream();
+                            self.walk_tokens(tokens, Kind::Invariant, uses);
+                        }
+                        TypeParamBound::Lifetime(lifetime) => {
+                            self.lifetime(lifetime, ambient, uses)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Type::Tuple(tuple) => tuple
+                .elems
+                .iter()
+                .for_each(|elem| self.walk(elem, ambient, uses)),
+            Type::Array(array) => self.walk(&array.elem, ambient, uses),
+            Type::Slice(slice) => self.walk(&slice.elem, ambient, uses),
+            Type::Paren(paren) => self.walk(&paren.elem, ambient, uses),
+            Type::Group(group) => self.walk(&group.elem, ambient, uses),
+            Type::Macro(mac) if mac.mac.path.is_ident("markers") => {
+                let _ = (|input: ParseStream| self.walk_markers(input, ambient, uses))
+                    .parse2(mac.mac.tokens.clone());
+            }
+            _ => {}
+        }
+    }
+
+    // Walks the arguments of a trait bound, all of which are invariant.
+    fn walk_tokens(&self, tokens: TokenStream, kind: Kind, uses: &mut Vec<Use<'a>>) {
+        for (p, param) in self.params.iter().enumerate() {
+            let mentioned = match param {
+                GenericParam::Type(ty) => mentions(&tokens, &ty.ident.to_string()),
+                GenericParam::Lifetime(def) => mentions(&tokens, &def.lifetime.ident.to_string()),
+                GenericParam::Const(_) => false,
+            };
+            if mentioned {
+                self.push(uses, p, kind, false);
+            }
+        }
+    }
+
+    // Walks the contents of a `markers![+'a, -T]` bundle.
+    fn walk_markers(
+        &self,
+        input: ParseStream,
+        ambient: Kind,
+        uses: &mut Vec<Use<'a>>,
+    ) -> syn::Result<()> {
+        while !input.is_empty() {
+            let kind = if input.parse::<Option<Token![+]>>()?.is_some() {
+                Kind::Covariant
+            } else if input.parse::<Option<Token![-]>>()?.is_some() {
+                Kind::Contravariant
+            } else {
+                input.parse::<Token![=]>()?;
+                Kind::Invariant
+            };
+            let param = if input.peek(Lifetime) {
+                let lifetime: Lifetime = input.parse()?;
+                self.lifetime_param(&lifetime)
+            } else {
+                let ty: Type = input.parse()?;
+                match &ty {
+                    Type::Path(path) if path.path.segments.len() == 1 => self.type_param(path),
+                    _ => {
+                        self.walk(&ty, ambient.compose(kind), uses);
+                        None
+                    }
+                }
+            };
+            if let Some(param) = param {
+                self.push(uses, param, ambient.compose(kind), true);
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn lifetime(&self, lifetime: &Lifetime, kind: Kind, uses: &mut Vec<Use<'a>>) {
+        if let Some(param) = self.lifetime_param(lifetime) {
+            self.push(uses, param, kind, false);
+        }
+    }
+
+    fn push(&self, uses: &mut Vec<Use<'a>>, param: usize, kind: Kind, marker: bool) {
+        uses.push(Use {
+            param,
+            kind,
+            marker,
+            field: self.field,
+        });
+    }
+
+    // The parameter named by a path such as `T` or `T::Item`.
+    fn type_param(&self, path: &syn::TypePath) -> Option<usize> {
+        let first = path.path.segments.first()?;
+        if !first.arguments.is_none() {
+            return None;
+        }
+        self.params.iter().position(|param| match param {
+            GenericParam::Type(ty) => ty.ident == first.ident,
+            _ => false,
+        })
+    }
+
+    fn lifetime_param(&self, lifetime: &Lifetime) -> Option<usize> {
+        self.params.iter().position(|param| match param {
+            GenericParam::Lifetime(def) => def.lifetime == *lifetime,
+            _ => false,
+        })
+    }
+
+    // The parameter marked by the argument of a marker, which is either the
+    // parameter itself, or `Lifetime<'a>` for a lifetime parameter.
+    fn marker_param(&self, arg: &GenericArgument) -> Option<usize> {
+        match arg {
+            GenericArgument::Lifetime(lifetime) => self.lifetime_param(lifetime),
+            GenericArgument::Type(Type::Path(path)) if path.qself.is_none() => {
+                let last = path.path.segments.last()?;
+                if path.path.segments.len() == 1 && last.arguments.is_none() {
+                    return self.type_param(path);
+                }
+                match &last.arguments {
+                    PathArguments::AngleBracketed(args) if last.ident == "Lifetime" => {
+                        match args.args.first() {
+                            Some(GenericArgument::Lifetime(lifetime)) => {
+                                self.lifetime_param(lifetime)
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+fn mentions(tokens: &TokenStream, name: &str) -> bool {
+    tokens.clone().into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => ident == name,
+        proc_macro2::TokenTree::Group(group) => mentions(&group.stream(), name),
+        _ => false,
+    })
+}
+
+// The variance declared by each of the markers of `type-variance`.
+fn marker_kind(name: &str) -> Option<Kind> {
+    match name {
+        "Covariant" | "CovariantLifetime" => Some(Kind::Covariant),
+        "Contravariant" | "ContravariantLifetime" => Some(Kind::Contravariant),
+        "Invariant" | "InvariantLifetime" => Some(Kind::Invariant),
+        _ => None,
+    }
+}
+
+// The variance of well-known types with respect to each of their arguments.
+//
+// NOTE: This must be kept identical to the table of `type_variance::infer`,
+// so that the derive and `cargo variance` agree. The two crates are packaged
+// separately, and so cannot share the file.
+fn builtin(name: &str) -> Option<&'static [Kind]> {
+    const CO: &[Kind] = &[Kind::Covariant; 4];
+    const CONTRA: &[Kind] = &[Kind::Contravariant];
+    const INV: &[Kind] = &[Kind::Invariant];
+
+    Some(match name {
+        "Cell" | "UnsafeCell" | "SyncUnsafeCell" | "RefCell" | "OnceCell" | "OnceLock"
+        | "Mutex" | "RwLock" | "AtomicPtr" | "Invariant" | "InvariantLifetime"
+        | "InvariantOwns" | "InvariantNonNull" | "InvariantBox" => INV,
+        "Contravariant" | "ContravariantLifetime" | "ContravariantNonNull" => CONTRA,
+        "BorrowsMut" | "MutexGuard" | "RwLockWriteGuard" | "RefMut" => {
+            &[Kind::Covariant, Kind::Invariant]
+        }
+        "FnMarker" => &[Kind::Contravariant, Kind::Covariant],
+        "Box" | "Vec" | "VecDeque" | "LinkedList" | "BinaryHeap" | "Rc" | "Arc" | "Weak"
+        | "Option" | "Result" | "NonNull" | "PhantomData" | "ManuallyDrop" | "MaybeUninit"
+        | "Pin" | "Reverse" | "Wrapping" | "Saturating" | "Cow" | "Ref"
+        | "HashMap" | "HashSet" | "BTreeMap" | "BTreeSet" | "Covariant"
+        | "CovariantLifetime" | "Lifetime" | "Owns" | "Borrows" | "HigherRanked"
+        | "Markers" => CO,
+        _ => return None,
+    })
+}
@@ -0,0 +1,80 @@
+//! Procedural macros for the [`type-variance`] crate.
+//!
+//! These are re-exported by `type-variance` when its `macros` feature is
+//! enabled, and should be used through that crate rather than directly.
+//!
+//! [`type-variance`]: https://docs.rs/type-variance
+
+extern crate proc_macro;
+
+mod attr;
+mod check;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemStruct};
+
+/// Declares the variance of a struct with respect to its generic parameters
+/// and injects the marker field that enforces it.
+///
+/// Each argument assigns one of `covariant`, `contravariant`, or `invariant`
+/// to a type or lifetime parameter of the struct:
+/// ```
+/// use type_variance::{variance, Covariant};
+///
+/// #[variance(Arg = contravariant, Ret = covariant, 'a = invariant)]
+/// struct Func<'a, Arg, Ret> {
+///     data: u32,
+/// }
+///
+/// fn make<'a, Arg, Ret>() -> Func<'a, Arg, Ret> {
+///     Func {
+///         data: 42,
+///         _variance: variance(),
+///     }
+/// }
+/// ```
+/// All of the requested markers are combined into a single field, named
+/// `_variance` by default, which can be initialized with
+/// `type_variance::variance()`. A different name can be chosen with the
+/// `field = name` argument. Tuple structs receive an extra positional field at
+/// the end instead.
+///
+/// Parameters that are not listed and are not used by any existing field are
+/// marked with the `default` variance, which is `invariant` unless specified
+/// otherwise, e.g. `#[variance(default = covariant)]`. Listed parameters are
+/// always marked, even if other fields use them too.
+#[proc_macro_attribute]
+pub fn variance(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as attr::Args);
+    let item = parse_macro_input!(input as ItemStruct);
+    attr::expand(args, item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Rejects fields which override the variance declared by a marker.
+///
+/// A marker such as `Contravariant<T>` only declares the variance of a type if
+/// no other field uses `T` differently. This derive inspects every field, and
+/// reports an error at each one which would silently change the declared
+/// variance:
+/// ```compile_fail
+/// use type_variance::{CheckVariance, Contravariant};
+///
+/// #[derive(CheckVariance)]
+/// struct Ref<'a, T> {
+///     inner: &'a T, // error: `T` is used covariantly by this field
+///     marker: Contravariant<T>,
+/// }
+/// ```
+/// Markers nested within other types, such as `Contravariant<fn(T)>`, and
+/// fields whose types are not known to the derive are not checked. The derive
+/// implements no traits, and can be combined with [`variance`](attr.variance.html)
+/// by placing it after the attribute.
+#[proc_macro_derive(CheckVariance)]
+pub fn check_variance(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    check::check(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,12 @@
+use type_variance::variance;
+
+#[variance(U = covariant)]
+struct Unknown<T>(T);
+
+#[variance(T = covariant, T = invariant)]
+struct Duplicate<T>(T);
+
+#[variance(T = bivariant)]
+struct Bivariant<T>(T);
+
+fn main() {}
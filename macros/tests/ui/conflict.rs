@@ -0,0 +1,31 @@
+use std::cell::Cell;
+use std::sync::OnceLock;
+
+use type_variance::{markers, CheckVariance, Contravariant, Covariant, Lifetime};
+
+#[derive(CheckVariance)]
+struct Ref<'a, T> {
+    inner: &'a T,
+    marker: Contravariant<T>,
+}
+
+#[derive(CheckVariance)]
+struct Shared<'a, T> {
+    markers: markers![+'a, +T],
+    cell: Cell<&'a ()>,
+    get: fn() -> Box<T>,
+}
+
+#[derive(CheckVariance)]
+enum Either<'a> {
+    Left(Covariant<Lifetime<'a>>),
+    Right(fn(&'a ())),
+}
+
+#[derive(CheckVariance)]
+struct Lazy<T> {
+    marker: Covariant<T>,
+    value: OnceLock<T>,
+}
+
+fn main() {}
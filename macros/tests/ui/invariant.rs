@@ -0,0 +1,12 @@
+use type_variance::variance;
+
+#[variance(T = invariant)]
+struct Opaque<T> {
+    inner: Box<T>,
+}
+
+fn invariant_fail<'a>(opaque: Opaque<&'static ()>) -> Opaque<&'a ()> {
+    opaque
+}
+
+fn main() {}
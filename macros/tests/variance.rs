@@ -0,0 +1,95 @@
+#![allow(dead_code, clippy::extra_unused_lifetimes)]
+
+use type_variance::{variance, CheckVariance, Contravariant, Covariant, Lifetime};
+
+#[variance(Arg = contravariant, Ret = covariant)]
+struct Func<Arg, Ret> {
+    data: u32,
+}
+
+#[variance('a = covariant)]
+struct Guard<'a>;
+
+#[variance(T = invariant)]
+struct Opaque<T: ?Sized>(Box<u32>);
+
+#[variance(field = marker)]
+struct Named<T> {
+    len: usize,
+}
+
+#[variance(default = covariant)]
+struct Slice<'a, T> {
+    len: usize,
+}
+
+#[test]
+fn co_contra<'a>() {
+    let _func: Func<Lifetime<'static>, Lifetime<'a>> = Func::<Lifetime<'a>, Lifetime<'static>> {
+        data: 42,
+        _variance: variance(),
+    };
+}
+
+#[test]
+fn lifetime<'a>() {
+    let _guard: Guard<'a> = Guard::<'static> { _variance: variance() };
+}
+
+#[test]
+fn tuple_struct() {
+    let opaque: Opaque<str> = Opaque(Box::new(42), variance());
+    assert_eq!(*opaque.0, 42);
+}
+
+#[test]
+fn field_name() {
+    let named: Named<u8> = Named { len: 0, marker: variance() };
+    assert_eq!(named.len, 0);
+}
+
+#[test]
+fn default<'a>() {
+    let slice: Slice<'static, &'static ()> = Slice { len: 0, _variance: variance() };
+    let _slice: Slice<'a, &'a ()> = slice;
+}
+
+#[test]
+fn used_params_are_untouched() {
+    #[variance(default = contravariant)]
+    struct Wrapper<T> {
+        inner: Covariant<T>,
+    }
+
+    let _wrapper: Wrapper<u8> = Wrapper { inner: variance() };
+}
+
+#[test]
+fn check_variance<'a>() {
+    #[derive(CheckVariance)]
+    struct Sink<'a, T> {
+        write: fn(&'a (), T),
+        marker: Contravariant<T>,
+        lifetime: Contravariant<Lifetime<'a>>,
+    }
+
+    #[variance(Arg = contravariant, Ret = covariant)]
+    #[derive(CheckVariance)]
+    struct Func<Arg, Ret> {
+        ret: Option<Ret>,
+    }
+
+    let sink: Sink<'a, &'a ()> = Sink {
+        write: |_, _| {},
+        marker: variance(),
+        lifetime: variance(),
+    };
+    let _sink: Sink<'static, &'static ()> = sink;
+    let _func: Func<u8, u16> = Func { ret: None, _variance: variance() };
+}
+
+#[test]
+fn failure_tests() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
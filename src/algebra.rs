@@ -0,0 +1,188 @@
+use crate::{Borrows, BorrowsMut, InvariantOwns, Owns};
+use crate::{Contravariant, ContravariantLifetime, Covariant, CovariantLifetime};
+use crate::{Co, Contra, Inv, Invariant, InvariantLifetime, TrustedVariance, Variance, VarianceTag};
+
+use self::ops::{HasTag, Ops};
+
+/// The marker for a type parameter used inside of another variant type.
+///
+/// If a type is `Outer`-variant with respect to some `X`, and `X` is in turn
+/// `Inner`-variant with respect to `T`, then the type is
+/// `Compose<Outer, Inner>`-variant with respect to `T`. Only the variance of
+/// `Outer` is used, while the result marks the parameter of `Inner`:
+///
+/// | `Outer`         | `Inner`         | `Compose<Outer, Inner>` |
+/// |-----------------|-----------------|-------------------------|
+/// | covariant       | any             | same as `Inner`         |
+/// | contravariant   | any             | flipped `Inner`         |
+/// | invariant       | any             | invariant               |
+///
+/// The result is always one of [`Covariant`], [`Contravariant`], and
+/// [`Invariant`]. Markers without a parameter, such as [`NotSend`], have no
+/// variance to compose.
+///
+/// [`Covariant`]: enum.Covariant.html
+/// [`Contravariant`]: enum.Contravariant.html
+/// [`Invariant`]: enum.Invariant.html
+/// [`NotSend`]: struct.NotSend.html
+///
+/// For example:
+/// ```
+/// use type_variance::{Compose, Contravariant, Covariant};
+///
+/// // A callback which is handed a consumer of `T`, like `fn(fn(T))`, is
+/// // covariant with respect to `T`.
+/// struct Visitor<T> {
+///     marker: Compose<Contravariant<()>, Contravariant<T>>,
+/// }
+///
+/// let _: Visitor<u8> = Visitor { marker: Covariant::new() };
+/// ```
+pub type Compose<Outer, Inner> = <Outer as ComposeWith<Inner>>::Output;
+
+/// The marker for a type parameter which is used in two places at once.
+///
+/// Uses with the same variance keep it, while mixed uses become invariant,
+/// as described in the [limitations](index.html#limitations) of the markers.
+/// Both markers must have the same [`Param`], which the result marks.
+///
+/// The result is always one of [`Covariant`], [`Contravariant`], and
+/// [`Invariant`].
+///
+/// [`Param`]: trait.Variance.html#associatedtype.Param
+/// [`Covariant`]: enum.Covariant.html
+/// [`Contravariant`]: enum.Contravariant.html
+/// [`Invariant`]: enum.Invariant.html
+///
+/// For example:
+/// ```
+/// use type_variance::{Borrows, Contravariant, Invariant, Join};
+///
+/// // Equivalent to `Ref` holding both a `&'a T` and a `Contravariant<T>`.
+/// struct Ref<'a, T> {
+///     marker: Join<Borrows<'a, T>, Contravariant<T>>,
+/// }
+///
+/// let _: Ref<u8> = Ref { marker: Invariant::new() };
+/// ```
+pub type Join<A, B> = <A as JoinWith<B>>::Output;
+
+/// A marker which can be composed with the marker `Inner`.
+///
+/// This is implemented for every marker with a parameter, and is used by the
+/// [`Compose`] alias.
+///
+/// [`Compose`]: type.Compose.html
+pub trait ComposeWith<Inner: Variance>: Variance {
+    /// The marker for the parameter of `Inner`, used inside of `Self`.
+    type Output: Variance;
+}
+
+/// A marker which can be joined with the marker `Other`.
+///
+/// This is implemented for every pair of markers with the same parameter, and
+/// is used by the [`Join`] alias.
+///
+/// [`Join`]: type.Join.html
+pub trait JoinWith<Other: Variance>: Variance {
+    /// The marker for a parameter used as both `Self` and `Other`.
+    type Output: Variance;
+}
+
+impl<Outer, Inner> ComposeWith<Inner> for Outer
+where
+    Outer: HasTag,
+    Inner: HasTag,
+{
+    type Output = <<Outer::Tag as Ops>::Compose<Inner::Tag> as VarianceTag>::Marker<Inner::Param>;
+}
+
+impl<A, B> JoinWith<B> for A
+where
+    A: HasTag,
+    B: HasTag<Param = A::Param>,
+{
+    type Output = <<A::Tag as Ops>::Join<B::Tag> as VarianceTag>::Marker<A::Param>;
+}
+
+// Evaluates the operators on the tag types by trait resolution.
+pub(crate) mod ops {
+    use crate::{Co, Contra, Inv, Variance, VarianceTag};
+
+    pub trait Ops: VarianceTag {
+        type Flip: Ops;
+        type Compose<Inner: Ops>: Ops;
+        type Join<Other: Ops>: Ops;
+
+        // The result of joining `Co` or `Contra` with `Self`.
+        type JoinCo: Ops;
+        type JoinContra: Ops;
+    }
+
+    impl Ops for Co {
+        type Flip = Contra;
+        type Compose<Inner: Ops> = Inner;
+        type Join<Other: Ops> = Other::JoinCo;
+        type JoinCo = Co;
+        type JoinContra = Inv;
+    }
+
+    impl Ops for Contra {
+        type Flip = Co;
+        type Compose<Inner: Ops> = Inner::Flip;
+        type Join<Other: Ops> = Other::JoinContra;
+        type JoinCo = Inv;
+        type JoinContra = Contra;
+    }
+
+    impl Ops for Inv {
+        type Flip = Inv;
+        type Compose<Inner: Ops> = Inv;
+        type Join<Other: Ops> = Inv;
+        type JoinCo = Inv;
+        type JoinContra = Inv;
+    }
+
+    /// The variance of a marker with a parameter.
+    pub trait HasTag: Variance {
+        type Tag: Ops;
+    }
+}
+
+impl<T: ?Sized> HasTag for Covariant<T> {
+    type Tag = Co;
+}
+impl<T: ?Sized> HasTag for Contravariant<T> {
+    type Tag = Contra;
+}
+impl<T: ?Sized> HasTag for Invariant<T> {
+    type Tag = Inv;
+}
+impl HasTag for CovariantLifetime<'_> {
+    type Tag = Co;
+}
+impl HasTag for ContravariantLifetime<'_> {
+    type Tag = Contra;
+}
+impl HasTag for InvariantLifetime<'_> {
+    type Tag = Inv;
+}
+impl<T: ?Sized> HasTag for Owns<T> {
+    type Tag = Co;
+}
+impl<T: ?Sized> HasTag for InvariantOwns<T> {
+    type Tag = Inv;
+}
+impl<T: ?Sized> HasTag for Borrows<'_, T> {
+    type Tag = Co;
+}
+impl<T: ?Sized> HasTag for BorrowsMut<'_, T> {
+    type Tag = Inv;
+}
+impl<M> HasTag for M
+where
+    M: TrustedVariance,
+    M::Tag: Ops,
+{
+    type Tag = M::Tag;
+}
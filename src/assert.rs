@@ -0,0 +1,230 @@
+/// Asserts at compile time that a type is [covariant] with respect to one of
+/// its parameters.
+///
+/// The parameter under test is written as a `_` placeholder, or as `'_` for a
+/// lifetime parameter. The macro expands to an unused function which coerces
+/// the type to one with a shorter lifetime substituted for the placeholder, so
+/// any change that makes the type less than covariant breaks the build.
+///
+/// [covariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+///
+/// For example:
+/// ```
+/// use type_variance::{assert_covariant, Covariant};
+///
+/// struct Producer<T> {
+///     id: u32,
+///     marker: Covariant<T>,
+/// }
+///
+/// assert_covariant!(Producer<_>);
+/// assert_covariant!(std::borrow::Cow<'_, str>);
+/// ```
+///
+/// The placeholder is replaced by `&'a ()` for a type parameter, so it must be
+/// possible to instantiate the type with such a reference.
+#[macro_export]
+macro_rules! assert_covariant {
+    ($($ty:tt)+) => {
+        $crate::__variance_check! { covariant [] [] false $($ty)+ }
+    };
+}
+
+/// Asserts at compile time that a type is [contravariant] with respect to one
+/// of its parameters.
+///
+/// This is the counterpart to [`assert_covariant!`], and uses the same `_`
+/// and `'_` placeholder syntax.
+///
+/// [contravariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+/// [`assert_covariant!`]: macro.assert_covariant.html
+///
+/// For example:
+/// ```
+/// use type_variance::{assert_contravariant, Contravariant};
+///
+/// struct Consumer<T> {
+///     id: u32,
+///     marker: Contravariant<T>,
+/// }
+///
+/// assert_contravariant!(Consumer<_>);
+/// ```
+#[macro_export]
+macro_rules! assert_contravariant {
+    ($($ty:tt)+) => {
+        $crate::__variance_check! { contravariant [] [] false $($ty)+ }
+    };
+}
+
+/// Asserts at compile time that the first type is a subtype of the second.
+///
+/// Lifetimes used by the types may be declared up front, along with any
+/// outlives bounds between them.
+///
+/// For example:
+/// ```
+/// use type_variance::{assert_subtype, Contravariant, Covariant};
+///
+/// assert_subtype!(&'static str, &'static str);
+/// assert_subtype!(<'a> Covariant<&'static str>, Covariant<&'a str>);
+/// assert_subtype!(<'a, 'b: 'a> Contravariant<&'a str>, Contravariant<&'b str>);
+/// ```
+#[macro_export]
+macro_rules! assert_subtype {
+    (<$($lt:lifetime $(: $bound:lifetime)?),* $(,)?> $sub:ty, $sup:ty $(,)?) => {
+        const _: () = {
+            #[allow(dead_code)]
+            fn assert_subtype<$($lt $(: $bound)?),*>(value: $sub) -> $sup {
+                value
+            }
+        };
+    };
+    ($sub:ty, $sup:ty $(,)?) => {
+        $crate::assert_subtype!(<> $sub, $sup);
+    };
+}
+
+// Replaces every `_` and `'_` placeholder with a long-lived and a short-lived
+// variant, then emits the coercion for the requested variance.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __variance_check {
+    ($mode:ident [$($long:tt)*] [$($short:tt)*] $found:tt _ $($rest:tt)*) => {
+        $crate::__variance_check! {
+            $mode [$($long)* &'__long ()] [$($short)* &'__short ()] true $($rest)*
+        }
+    };
+    ($mode:ident [$($long:tt)*] [$($short:tt)*] $found:tt '_ $($rest:tt)*) => {
+        $crate::__variance_check! {
+            $mode [$($long)* '__long] [$($short)* '__short] true $($rest)*
+        }
+    };
+    ($mode:ident [$($long:tt)*] [$($short:tt)*] $found:tt $tok:tt $($rest:tt)*) => {
+        $crate::__variance_check! {
+            $mode [$($long)* $tok] [$($short)* $tok] $found $($rest)*
+        }
+    };
+    ($mode:ident [$($long:tt)*] [$($short:tt)*] false) => {
+        compile_error!("expected a `_` or `'_` placeholder for the parameter under test");
+    };
+    (covariant [$($long:tt)*] [$($short:tt)*] true) => {
+        $crate::assert_subtype!(<'__short, '__long: '__short> $($long)*, $($short)*);
+    };
+    (contravariant [$($long:tt)*] [$($short:tt)*] true) => {
+        $crate::assert_subtype!(<'__short, '__long: '__short> $($short)*, $($long)*);
+    };
+}
+
+/// Generates doctests which check the _exact_ variance of one or more types.
+///
+/// The assertion macros can only show that a type is at least as permissive
+/// as expected. To show that it is no more permissive, the forbidden
+/// coercions must fail to compile. This macro emits a module whose
+/// documentation contains a `compile_fail` doctest for every forbidden
+/// direction, alongside a regular doctest for every allowed one. They are run
+/// by `cargo test` like any other doctest.
+///
+/// Each entry is one of `covariant`, `contravariant`, or `invariant`, applied
+/// to a type with a `_` or `'_` placeholder for the parameter under test.
+/// Since doctests are compiled as a separate crate, the types must be written
+/// with paths that resolve from outside of the crate.
+///
+/// For example:
+/// ```
+/// # mod my_crate {
+/// #     pub struct In<T>(pub type_variance::Invariant<T>);
+/// #     pub struct Co<'a>(pub &'a ());
+/// # }
+/// #[cfg(doctest)]
+/// type_variance::variance_suite! {
+///     mod variance_tests {
+///         invariant(my_crate::In<_>);
+///         covariant(my_crate::Co<'_>);
+///     }
+/// }
+/// ```
+/// Gating the invocation on `cfg(doctest)` keeps the generated module out of
+/// regular builds and out of the crate's documentation.
+#[macro_export]
+macro_rules! variance_suite {
+    (
+        $(#[$attr:meta])*
+        $vis:vis mod $name:ident {
+            $($kind:ident($($ty:tt)+);)*
+        }
+    ) => {
+        $(#[$attr])*
+        $(#[doc = $crate::__variance_suite!($kind [$($ty)+] [] [] false $($ty)+)])*
+        #[allow(dead_code)]
+        $vis mod $name {}
+    };
+}
+
+// Renders the documentation for a single entry of `variance_suite!`.
+//
+// State: kind [type as written] [long substitution] [short substitution]
+//        placeholder-found input...
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __variance_suite {
+    ($kind:ident $ty:tt [$($long:tt)*] [$($short:tt)*] $found:tt _ $($rest:tt)*) => {
+        $crate::__variance_suite! {
+            $kind $ty [$($long)* &'__long ()] [$($short)* &'__short ()] true $($rest)*
+        }
+    };
+    ($kind:ident $ty:tt [$($long:tt)*] [$($short:tt)*] $found:tt '_ $($rest:tt)*) => {
+        $crate::__variance_suite! {
+            $kind $ty [$($long)* '__long] [$($short)* '__short] true $($rest)*
+        }
+    };
+    ($kind:ident $ty:tt [$($long:tt)*] [$($short:tt)*] $found:tt $tok:tt $($rest:tt)*) => {
+        $crate::__variance_suite! {
+            $kind $ty [$($long)* $tok] [$($short)* $tok] $found $($rest)*
+        }
+    };
+    ($kind:ident $ty:tt $long:tt $short:tt false) => {
+        compile_error!("expected a `_` or `'_` placeholder for the parameter under test")
+    };
+    (covariant [$($ty:tt)*] $long:tt $short:tt true) => {
+        concat!(
+            "`", stringify!($($ty)*), "` is covariant.\n\n",
+            $crate::__variance_suite!(@check "" $long $short),
+            $crate::__variance_suite!(@check "compile_fail" $short $long),
+        )
+    };
+    (contravariant [$($ty:tt)*] $long:tt $short:tt true) => {
+        concat!(
+            "`", stringify!($($ty)*), "` is contravariant.\n\n",
+            $crate::__variance_suite!(@check "" $short $long),
+            $crate::__variance_suite!(@check "compile_fail" $long $short),
+        )
+    };
+    (invariant [$($ty:tt)*] $long:tt $short:tt true) => {
+        concat!(
+            "`", stringify!($($ty)*), "` is invariant.\n\n",
+            $crate::__variance_suite!(@check "" $long $long),
+            $crate::__variance_suite!(@check "compile_fail" $long $short),
+            $crate::__variance_suite!(@check "compile_fail" $short $long),
+        )
+    };
+    ($kind:ident $ty:tt $long:tt $short:tt true) => {
+        compile_error!(concat!(
+            "expected one of `covariant`, `contravariant`, or `invariant`, found `",
+            stringify!($kind),
+            "`",
+        ))
+    };
+
+    // Renders a doctest which coerces the first type into the second.
+    (@check $attr:literal [$($from:tt)*] [$($to:tt)*]) => {
+        concat!(
+            "```", $attr, "\n",
+            "fn check<'__short, '__long: '__short>(value: ", stringify!($($from)*), ")",
+            " -> ", stringify!($($to)*), " {\n",
+            "    value\n",
+            "}\n",
+            "```\n\n",
+        )
+    };
+}
@@ -0,0 +1,107 @@
+use core::marker::{PhantomData, PhantomPinned};
+
+use crate::{private, Variance, VarianceKind};
+
+/// Zero-sized type used to opt a type out of [`Send`].
+///
+/// The type remains [`Sync`], unlike when using a `PhantomData<*const ()>`.
+///
+/// [`Send`]: https://doc.rust-lang.org/stable/std/marker/trait.Send.html
+/// [`Sync`]: https://doc.rust-lang.org/stable/std/marker/trait.Sync.html
+///
+/// For example:
+/// ```
+/// use type_variance::{Invariant, NotSend};
+///
+/// struct Handle<T> {
+///     raw: usize,
+///     marker: Invariant<T>,
+///     thread_bound: NotSend,
+/// }
+/// ```
+#[derive(Default)]
+pub struct NotSend {
+    marker: PhantomData<*const ()>,
+}
+
+// SAFETY: `NotSend` holds no data, so sharing a reference to it between
+// threads cannot cause a data race.
+unsafe impl Sync for NotSend {}
+
+/// Zero-sized type used to opt a type out of [`Sync`].
+///
+/// The type remains [`Send`].
+///
+/// [`Send`]: https://doc.rust-lang.org/stable/std/marker/trait.Send.html
+/// [`Sync`]: https://doc.rust-lang.org/stable/std/marker/trait.Sync.html
+#[derive(Default)]
+pub struct NotSync {
+    marker: PhantomData<*const ()>,
+}
+
+// SAFETY: `NotSync` holds no data, so moving it to another thread cannot
+// cause a data race.
+unsafe impl Send for NotSync {}
+
+/// Zero-sized type used to opt a type out of [`Unpin`].
+///
+/// This is equivalent to [`PhantomPinned`], and is provided for symmetry with
+/// the other markers.
+///
+/// [`Unpin`]: https://doc.rust-lang.org/stable/std/marker/trait.Unpin.html
+/// [`PhantomPinned`]: https://doc.rust-lang.org/stable/std/marker/struct.PhantomPinned.html
+#[derive(Default)]
+pub struct NotUnpin {
+    marker: PhantomData<PhantomPinned>,
+}
+
+/// Zero-sized type used to opt a type out of [`UnwindSafe`].
+///
+/// The type remains [`RefUnwindSafe`].
+///
+/// [`UnwindSafe`]: https://doc.rust-lang.org/stable/std/panic/trait.UnwindSafe.html
+/// [`RefUnwindSafe`]: https://doc.rust-lang.org/stable/std/panic/trait.RefUnwindSafe.html
+#[derive(Default)]
+pub struct NotUnwindSafe {
+    marker: PhantomData<&'static mut ()>,
+}
+
+impl_marker_new!([] NotSend, Self { marker: PhantomData });
+impl_marker_new!([] NotSync, Self { marker: PhantomData });
+impl_marker_new!([] NotUnpin, Self { marker: PhantomData });
+impl_marker_new!([] NotUnwindSafe, Self { marker: PhantomData });
+
+impl_marker_traits!([] NotSend, "NotSend");
+impl_marker_traits!([] NotSync, "NotSync");
+impl_marker_traits!([] NotUnpin, "NotUnpin");
+impl_marker_traits!([] NotUnwindSafe, "NotUnwindSafe");
+
+impl private::Sealed for NotSend {}
+impl private::Sealed for NotSync {}
+impl private::Sealed for NotUnpin {}
+impl private::Sealed for NotUnwindSafe {}
+
+impl Variance for NotSend {
+    type Param = ();
+    type Flipped = Self;
+    type WithParam<U: ?Sized> = Self;
+    const KIND: VarianceKind = VarianceKind::Bivariant;
+}
+impl Variance for NotSync {
+    type Param = ();
+    type Flipped = Self;
+    type WithParam<U: ?Sized> = Self;
+    const KIND: VarianceKind = VarianceKind::Bivariant;
+}
+impl Variance for NotUnpin {
+    type Param = ();
+    type Flipped = Self;
+    type WithParam<U: ?Sized> = Self;
+    const KIND: VarianceKind = VarianceKind::Bivariant;
+}
+impl Variance for NotUnwindSafe {
+    type Param = ();
+    type Flipped = Self;
+    type WithParam<U: ?Sized> = Self;
+    const KIND: VarianceKind = VarianceKind::Bivariant;
+}
@@ -0,0 +1,153 @@
+use core::marker::PhantomData;
+
+use crate::{private, Variance, VarianceKind};
+
+/// A bundle of markers for several parameters, held in a single field.
+///
+/// `M` is usually a tuple of markers, for which [`Variance`] is implemented
+/// up to a length of 12. The bundle is itself a marker, and so may be built
+/// with [`variance`] in one call. The [`markers!`] macro provides a shorthand
+/// for naming the bundle type.
+///
+/// [`Variance`]: trait.Variance.html
+/// [`variance`]: fn.variance.html
+/// [`markers!`]: macro.markers.html
+///
+/// For example:
+/// ```
+/// use type_variance::{variance, Contravariant, Covariant, Invariant, Markers};
+///
+/// struct Table<K, V, S> {
+///     raw: *mut u8,
+///     marker: Markers<(Invariant<K>, Covariant<V>, Contravariant<S>)>,
+/// }
+///
+/// let table: Table<u8, u16, u32> = Table {
+///     raw: std::ptr::null_mut(),
+///     marker: variance(),
+/// };
+/// ```
+///
+/// The [`KIND`] of a tuple, and so of a bundle, is the join of the kinds of
+/// its elements. This only describes the bundle when all of the elements
+/// share a parameter: `(Covariant<A>, Contravariant<B>)` is covariant in `A`
+/// and contravariant in `B`, but its `KIND` is `Invariant`.
+///
+/// [`KIND`]: trait.Variance.html#associatedconstant.KIND
+// NOTE: The markers are held in a `PhantomData`, which has the same variance,
+// auto traits and drop check behaviour as `M` itself, so that the bundle can
+// be created in `const` contexts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Markers<M> {
+    markers: PhantomData<M>,
+}
+
+impl_marker_new!([M: Variance] Markers<M>, Markers { markers: PhantomData });
+
+impl<M: Variance> Markers<M> {
+    /// Returns the bundled markers.
+    pub fn into_inner(self) -> M {
+        M::default()
+    }
+}
+
+impl<M: Variance> From<M> for Markers<M> {
+    fn from(_: M) -> Self {
+        Self::MARKER
+    }
+}
+
+impl<M: Variance> private::Sealed for Markers<M> {}
+
+impl<M: Variance> Variance for Markers<M> {
+    type Param = M::Param;
+    type Flipped = Markers<M::Flipped>;
+    type WithParam<U: ?Sized> = Markers<M::WithParam<U>>;
+    const KIND: VarianceKind = M::KIND;
+}
+
+/// Names a [`Markers`] bundle with one marker per listed parameter.
+///
+/// Each parameter is prefixed with its variance: `+` for covariant, `-` for
+/// contravariant, and `=` for invariant. Both type and lifetime parameters may
+/// be listed.
+///
+/// [`Markers`]: struct.Markers.html
+///
+/// For example:
+/// ```
+/// use type_variance::{markers, variance};
+///
+/// struct Query<'db, Row, Filter, State> {
+///     handle: usize,
+///     marker: markers![+'db, +Row, -Filter, =State],
+/// }
+///
+/// let query: Query<u8, u16, u32> = Query {
+///     handle: 0,
+///     marker: variance(),
+/// };
+/// ```
+/// The `marker` field above has the type
+/// `Markers<(CovariantLifetime<'db>, Covariant<Row>, Contravariant<Filter>,
+/// Invariant<State>)>`.
+#[macro_export]
+macro_rules! markers {
+    ($($params:tt)*) => {
+        $crate::__markers!([] $($params)*)
+    };
+}
+
+// Converts each parameter of `markers!` to its marker, in order.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __markers {
+    ([$($done:ty,)*]) => {
+        $crate::Markers<($($done,)*)>
+    };
+    ([$($done:ty,)*] + $lt:lifetime $(, $($rest:tt)*)?) => {
+        $crate::__markers!([$($done,)* $crate::CovariantLifetime<$lt>,] $($($rest)*)?)
+    };
+    ([$($done:ty,)*] - $lt:lifetime $(, $($rest:tt)*)?) => {
+        $crate::__markers!([$($done,)* $crate::ContravariantLifetime<$lt>,] $($($rest)*)?)
+    };
+    ([$($done:ty,)*] = $lt:lifetime $(, $($rest:tt)*)?) => {
+        $crate::__markers!([$($done,)* $crate::InvariantLifetime<$lt>,] $($($rest)*)?)
+    };
+    ([$($done:ty,)*] + $param:ty $(, $($rest:tt)*)?) => {
+        $crate::__markers!([$($done,)* $crate::Covariant<$param>,] $($($rest)*)?)
+    };
+    ([$($done:ty,)*] - $param:ty $(, $($rest:tt)*)?) => {
+        $crate::__markers!([$($done,)* $crate::Contravariant<$param>,] $($($rest)*)?)
+    };
+    ([$($done:ty,)*] = $param:ty $(, $($rest:tt)*)?) => {
+        $crate::__markers!([$($done,)* $crate::Invariant<$param>,] $($($rest)*)?)
+    };
+}
+
+// Implements `Variance` for a tuple of markers. The tuple is as variant as
+// the join of its elements, which is only meaningful if they share a `Param`.
+macro_rules! impl_tuple_variance {
+    ($($name:ident)*) => {
+        impl<$($name: Variance),*> private::Sealed for ($($name,)*) {}
+
+        impl<$($name: Variance),*> Variance for ($($name,)*) {
+            type Param = ($(PhantomData<$name::Param>,)*);
+            type Flipped = ($($name::Flipped,)*);
+            type WithParam<U: ?Sized> = ($($name::WithParam<U>,)*);
+            const KIND: VarianceKind = VarianceKind::Bivariant $(.join($name::KIND))*;
+        }
+    };
+}
+
+macro_rules! impl_tuples {
+    () => {
+        impl_tuple_variance!();
+    };
+    ($head:ident $($tail:ident)*) => {
+        impl_tuple_variance!($head $($tail)*);
+        impl_tuples!($($tail)*);
+    };
+}
+
+impl_tuples!(A B C D E F G H I J K L);
@@ -0,0 +1,155 @@
+/// Declares a struct with the variance of each parameter written inline.
+///
+/// Each type or lifetime parameter is prefixed with its variance, as in
+/// [`markers!`]: `+` for covariant, `-` for contravariant, and `=` for
+/// invariant. The struct receives one more field, `_variance`, bundling the
+/// markers for all of its parameters, along with a `new` constructor which
+/// takes the remaining fields in order and fills in the markers:
+/// ```
+/// use type_variance::variance_struct;
+///
+/// variance_struct! {
+///     /// A callback along with its state.
+///     #[derive(Debug)]
+///     pub struct Func<+'a, -Arg, +Ret, =State> {
+///         pub name: &'a str,
+///         state: usize,
+///     }
+/// }
+///
+/// let func: Func<u8, u16, u32> = Func::new("len", 0);
+/// assert_eq!(func.name, "len");
+/// ```
+/// Bounds on the parameters are written in a `where` clause, which also
+/// applies to the constructor:
+/// ```
+/// # use type_variance::variance_struct;
+/// #
+/// variance_struct! {
+///     struct Handle<+T> where T: ?Sized {
+///         id: u32,
+///     }
+/// }
+///
+/// let _: Handle<str> = Handle::new(7);
+/// ```
+/// Only structs with named fields and at least one parameter are supported.
+/// As with [`Markers`], the declared variance is only kept if the other fields
+/// do not use the parameters differently. The constructor is a `const fn`
+/// with the same visibility as the struct.
+///
+/// [`markers!`]: macro.markers.html
+/// [`Markers`]: struct.Markers.html
+#[macro_export]
+macro_rules! variance_struct {
+    // `<-` and `<=` are lexed as single tokens, and so are split here.
+    ($(#[$attr:meta])* $vis:vis struct $name:ident <- $($rest:tt)*) => {
+        $crate::variance_struct! { $(#[$attr])* $vis struct $name < - $($rest)* }
+    };
+    ($(#[$attr:meta])* $vis:vis struct $name:ident <= $($rest:tt)*) => {
+        $crate::variance_struct! { $(#[$attr])* $vis struct $name < = $($rest)* }
+    };
+    ($(#[$attr:meta])* $vis:vis struct $name:ident < $($rest:tt)*) => {
+        $crate::__variance_struct! { @params [$(#[$attr])*] [$vis] $name [] $($rest)* }
+    };
+}
+
+// Collects the parameters of `variance_struct!` one at a time, since a
+// trailing comma cannot be matched after a repetition of `tt`s. Each
+// parameter is then converted to its marker, as in `markers!`, and the
+// `where` clause is collected one token at a time until only the fields
+// remain. The bundle is named directly, since derives cannot be used on
+// fields whose types are macros.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __variance_struct {
+    (@params $attrs:tt $vis:tt $name:tt [$($variance:tt $param:tt)+] $(,)? > $($rest:tt)*) => {
+        $crate::__variance_struct! {
+            @markers $attrs $vis $name [$($param),+] [] [$($variance $param),+] $($rest)*
+        }
+    };
+    (@params $attrs:tt $vis:tt $name:tt [$($done:tt)*] $variance:tt $param:tt , $($rest:tt)*) => {
+        $crate::__variance_struct! { @params $attrs $vis $name [$($done)* $variance $param] $($rest)* }
+    };
+    (@params $attrs:tt $vis:tt $name:tt [$($done:tt)*] $variance:tt $param:tt > $($rest:tt)*) => {
+        $crate::__variance_struct! {
+            @params $attrs $vis $name [$($done)* $variance $param] > $($rest)*
+        }
+    };
+    (@markers $attrs:tt $vis:tt $name:tt $params:tt [$($done:ty,)*]
+        [+ $lt:lifetime $(, $($todo:tt)*)?] $($rest:tt)*) => {
+        $crate::__variance_struct! {
+            @markers $attrs $vis $name $params [$($done,)* $crate::CovariantLifetime<$lt>,]
+            [$($($todo)*)?] $($rest)*
+        }
+    };
+    (@markers $attrs:tt $vis:tt $name:tt $params:tt [$($done:ty,)*]
+        [- $lt:lifetime $(, $($todo:tt)*)?] $($rest:tt)*) => {
+        $crate::__variance_struct! {
+            @markers $attrs $vis $name $params [$($done,)* $crate::ContravariantLifetime<$lt>,]
+            [$($($todo)*)?] $($rest)*
+        }
+    };
+    (@markers $attrs:tt $vis:tt $name:tt $params:tt [$($done:ty,)*]
+        [= $lt:lifetime $(, $($todo:tt)*)?] $($rest:tt)*) => {
+        $crate::__variance_struct! {
+            @markers $attrs $vis $name $params [$($done,)* $crate::InvariantLifetime<$lt>,]
+            [$($($todo)*)?] $($rest)*
+        }
+    };
+    (@markers $attrs:tt $vis:tt $name:tt $params:tt [$($done:ty,)*]
+        [+ $param:ident $(, $($todo:tt)*)?] $($rest:tt)*) => {
+        $crate::__variance_struct! {
+            @markers $attrs $vis $name $params [$($done,)* $crate::Covariant<$param>,]
+            [$($($todo)*)?] $($rest)*
+        }
+    };
+    (@markers $attrs:tt $vis:tt $name:tt $params:tt [$($done:ty,)*]
+        [- $param:ident $(, $($todo:tt)*)?] $($rest:tt)*) => {
+        $crate::__variance_struct! {
+            @markers $attrs $vis $name $params [$($done,)* $crate::Contravariant<$param>,]
+            [$($($todo)*)?] $($rest)*
+        }
+    };
+    (@markers $attrs:tt $vis:tt $name:tt $params:tt [$($done:ty,)*]
+        [= $param:ident $(, $($todo:tt)*)?] $($rest:tt)*) => {
+        $crate::__variance_struct! {
+            @markers $attrs $vis $name $params [$($done,)* $crate::Invariant<$param>,]
+            [$($($todo)*)?] $($rest)*
+        }
+    };
+    (@markers $attrs:tt $vis:tt $name:tt $params:tt [$($done:ty,)*] [] $($rest:tt)*) => {
+        $crate::__variance_struct! {
+            @where $attrs $vis $name $params [$crate::Markers<($($done,)*)>] [] $($rest)*
+        }
+    };
+    (
+        @where [$(#[$attr:meta])*] [$vis:vis] $name:ident [$($param:tt),+] [$markers:ty]
+        [$($where:tt)*] {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident : $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name<$($param),+> $($where)* {
+            $($(#[$field_attr])* $field_vis $field: $ty,)*
+            _variance: $markers,
+        }
+
+        impl<$($param),+> $name<$($param),+> $($where)* {
+            /// Creates the struct from its fields, along with its variance
+            /// markers.
+            #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+            $vis const fn new($($field: $ty),*) -> Self {
+                Self {
+                    $($field,)*
+                    _variance: $crate::Markers::new(),
+                }
+            }
+        }
+    };
+    (@where $attrs:tt $vis:tt $name:tt $params:tt $markers:tt [$($where:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__variance_struct! {
+            @where $attrs $vis $name $params $markers [$($where)* $next] $($rest)*
+        }
+    };
+}
@@ -0,0 +1,145 @@
+use core::any::type_name;
+use core::marker::PhantomData;
+
+use crate::{private, Covariant, Variance, VarianceKind};
+
+/// Zero-sized type used to mark a type as having the variance of the function
+/// pointer `fn(A, B, ...) -> Ret`, where `Args` is the tuple `(A, B, ...)`.
+///
+/// The type is [contravariant] with respect to each of the argument types, and
+/// [covariant] with respect to `Ret`. This makes it a natural fit for handles
+/// to callbacks which are held behind raw pointers, since the handle can be
+/// given exactly the subtyping behavior of the function it stands in for.
+///
+/// [contravariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+/// [covariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+///
+/// For example:
+/// ```
+/// use type_variance::FnMarker;
+///
+/// // Behaves like `fn(&Event, u32) -> Response` with respect to subtyping.
+/// struct Callback<Event, Response> {
+///     raw: *const (),
+///     marker: FnMarker<(Event, u32), Response>,
+/// }
+/// ```
+/// For arguments given as a tuple of up to 12 types, the marker converts to
+/// and from a `PhantomData` of the corresponding function pointer type.
+///
+/// As a [`Variance`] marker, its parameter is `Args`, with respect to which
+/// it is contravariant. `WithParam<U>` keeps the return type, but the
+/// flipped marker is the plain `Covariant<Args>`, since no function pointer
+/// is covariant in its arguments.
+///
+/// [`Variance`]: trait.Variance.html
+pub struct FnMarker<Args: ?Sized, Ret: ?Sized = ()> {
+    marker: PhantomData<fn(Args) -> Ret>,
+}
+
+impl<Args: ?Sized, Ret: ?Sized> Default for FnMarker<Args, Ret> {
+    fn default() -> Self {
+        Self::MARKER
+    }
+}
+
+impl_marker_new!([Args: ?Sized, Ret: ?Sized] FnMarker<Args, Ret>, Self { marker: PhantomData });
+
+impl_marker_traits!(
+    [Args: ?Sized, Ret: ?Sized] FnMarker<Args, Ret>,
+    "FnMarker<{}, {}>",
+    type_name::<Args>(),
+    type_name::<Ret>()
+);
+
+impl<Args: ?Sized, Ret: ?Sized> private::Sealed for FnMarker<Args, Ret> {}
+
+impl<Args: ?Sized, Ret: ?Sized> Variance for FnMarker<Args, Ret> {
+    type Param = Args;
+    type Flipped = Covariant<Args>;
+    type WithParam<U: ?Sized> = FnMarker<U, Ret>;
+    const KIND: VarianceKind = VarianceKind::Contravariant;
+}
+
+/// A sealed trait implemented by tuples of up to 12 types, which names the
+/// function pointer type taking them as arguments.
+///
+/// For example, `<(A, B) as FnArgs>::Pointer<R>` is `fn(A, B) -> R`.
+pub trait FnArgs: sealed::Sealed {
+    /// The function pointer type with these arguments and return type `Ret`.
+    type Pointer<Ret>: Copy;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+impl<Args: FnArgs, Ret> From<PhantomData<Args::Pointer<Ret>>> for FnMarker<Args, Ret> {
+    fn from(_: PhantomData<Args::Pointer<Ret>>) -> Self {
+        Self::MARKER
+    }
+}
+
+impl<Args: FnArgs, Ret> From<FnMarker<Args, Ret>> for PhantomData<Args::Pointer<Ret>> {
+    fn from(_: FnMarker<Args, Ret>) -> Self {
+        PhantomData
+    }
+}
+
+macro_rules! impl_fn_args {
+    ($($name:ident)*) => {
+        impl<$($name),*> sealed::Sealed for ($($name,)*) {}
+
+        impl<$($name),*> FnArgs for ($($name,)*) {
+            type Pointer<Ret> = fn($($name),*) -> Ret;
+        }
+    };
+}
+
+macro_rules! impl_fn_args_tuples {
+    () => {
+        impl_fn_args!();
+    };
+    ($head:ident $($tail:ident)*) => {
+        impl_fn_args!($head $($tail)*);
+        impl_fn_args_tuples!($($tail)*);
+    };
+}
+
+impl_fn_args_tuples!(A B C D E F G H I J K L);
+
+/// A marker for a type behaving like the function pointer type `F`, which may
+/// be higher-ranked.
+///
+/// Since the arguments of [`FnMarker`] are named one by one, it cannot bind a
+/// lifetime for the whole signature as in `for<'a> fn(&'a T) -> &'a U`. A
+/// [`Covariant`] marker of the function pointer type itself keeps the
+/// subtyping between function pointer types instead, including that of
+/// higher-ranked ones: `for<'a> fn(&'a T)` is a subtype of `fn(&'static T)`,
+/// and so `Covariant<for<'a> fn(&'a T)>` is a subtype of
+/// `Covariant<fn(&'static T)>`. This alias only gives that use a name.
+///
+/// [`FnMarker`]: struct.FnMarker.html
+/// [`Covariant`]: enum.Covariant.html
+///
+/// For example:
+/// ```
+/// use type_variance::Covariant;
+///
+/// // A parser for any input lifetime, held behind a raw pointer.
+/// struct Parser<T, U> {
+///     raw: *const (),
+///     marker: Covariant<for<'a> fn(&'a T) -> &'a U>,
+/// }
+///
+/// // It may be used where a parser for one particular lifetime is expected.
+/// struct Local<'a, T, U> {
+///     raw: *const (),
+///     marker: Covariant<fn(&'a T) -> &'a U>,
+/// }
+///
+/// fn localize<'a, T, U>(parser: Parser<T, U>) -> Local<'a, T, U> {
+///     Local { raw: parser.raw, marker: parser.marker }
+/// }
+/// ```
+pub type HigherRanked<F> = Covariant<F>;
@@ -0,0 +1,699 @@
+//! Inference of the variance of type definitions, outside of the compiler.
+//!
+//! The definitions are described by a small IR of [`Item`]s, whose fields are
+//! given as [`Ty`]s. [`infer`] then computes the variance of every generic
+//! parameter the way rustc does: each use of a parameter within a field
+//! implies a variance, which is composed through the types that the use is
+//! nested in, and the uses are joined to find the variance of the parameter.
+//! Since the items may refer to each other, the variances are solved as a
+//! fixed point, starting from bivariance.
+//!
+//! Each result comes with the fields that caused it, so that the variance of
+//! a type can be explained as well as computed:
+//! ```
+//! use type_variance::infer::{infer, Field, Item, Ty};
+//! use type_variance::VarianceKind;
+//!
+//! // struct Slot<'a, T> { value: Cell<T>, owner: &'a str }
+//! let slot = Item {
+//!     name: "Slot".into(),
+//!     params: vec!["'a".into(), "T".into()],
+//!     fields: vec![
+//!         Field::new("value", Ty::path("Cell", vec![Ty::param("T")])),
+//!         Field::new("owner", Ty::reference(Some("'a"), false, Ty::path("str", vec![]))),
+//!     ],
+//! };
+//!
+//! let solution = infer(&[slot]).unwrap();
+//! let t = solution.get("Slot").unwrap().get("T").unwrap();
+//! assert_eq!(t.kind, VarianceKind::Invariant);
+//! assert_eq!(t.to_string(), "invariant because of field `value: Cell<T>`");
+//! ```
+//!
+//! [`Item`]: struct.Item.html
+//! [`Ty`]: enum.Ty.html
+//! [`infer`]: fn.infer.html
+//!
+//! Types which are not among the items are looked up by the last segment of
+//! their path in a table of well-known types from the standard library and
+//! from this crate, such as `Cell`, `Box`, and `Covariant`. Any other type is
+//! assumed to be invariant with respect to its arguments, which is the most
+//! conservative choice, and the assumption is noted in the causes.
+
+use std::borrow::ToOwned;
+use std::boxed::Box;
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use crate::VarianceKind::{self, Bivariant, Contravariant, Covariant, Invariant};
+
+/// A type in the IR.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Ty {
+    /// A generic type parameter of the item, such as `T`.
+    Param(String),
+    /// A lifetime used as a generic argument, such as `'a` or `'static`.
+    Lifetime(String),
+    /// A named type with its generic arguments, such as `Cell<T>` or
+    /// `Ref<'a, T>`. Types without arguments, such as `u8`, are also paths.
+    Path {
+        /// The path of the type, such as `Cell` or `std::cell::Cell`.
+        name: String,
+        /// The lifetime and type arguments, in order.
+        args: Vec<Ty>,
+    },
+    /// A reference `&'a T` or `&'a mut T`.
+    Ref {
+        /// The lifetime of the reference, if written.
+        lifetime: Option<String>,
+        /// Whether the reference is mutable.
+        mutable: bool,
+        /// The referenced type.
+        ty: Box<Ty>,
+    },
+    /// A raw pointer `*const T` or `*mut T`.
+    Ptr {
+        /// Whether the pointer is `*mut`.
+        mutable: bool,
+        /// The pointee type.
+        ty: Box<Ty>,
+    },
+    /// A function pointer `fn(A, B) -> R`.
+    Fn {
+        /// The argument types.
+        args: Vec<Ty>,
+        /// The return type, which is `()` if not written.
+        ret: Box<Ty>,
+    },
+    /// A trait object `dyn Trait<A, B> + 'a`.
+    Dyn {
+        /// The path of the trait.
+        name: String,
+        /// The generic arguments of the trait, including associated types.
+        args: Vec<Ty>,
+        /// The lifetime bound of the object, if written.
+        lifetime: Option<String>,
+    },
+    /// A tuple `(A, B)`.
+    Tuple(Vec<Ty>),
+    /// An array `[T; N]`, with its length as written.
+    Array(Box<Ty>, String),
+    /// A slice `[T]`.
+    Slice(Box<Ty>),
+    /// An associated type `<T as Trait>::Name`, with the name of the
+    /// associated type.
+    Projection(Box<Ty>, String),
+}
+
+impl Ty {
+    /// Creates a type parameter.
+    pub fn param(name: &str) -> Self {
+        Ty::Param(name.to_owned())
+    }
+
+    /// Creates a lifetime argument.
+    pub fn lifetime(name: &str) -> Self {
+        Ty::Lifetime(name.to_owned())
+    }
+
+    /// Creates a named type with the given arguments.
+    pub fn path(name: &str, args: Vec<Ty>) -> Self {
+        Ty::Path { name: name.to_owned(), args }
+    }
+
+    /// Creates a reference.
+    pub fn reference(lifetime: Option<&str>, mutable: bool, ty: Ty) -> Self {
+        Ty::Ref { lifetime: lifetime.map(ToOwned::to_owned), mutable, ty: Box::new(ty) }
+    }
+
+    /// Creates a raw pointer.
+    pub fn pointer(mutable: bool, ty: Ty) -> Self {
+        Ty::Ptr { mutable, ty: Box::new(ty) }
+    }
+
+    /// Creates a function pointer.
+    pub fn function(args: Vec<Ty>, ret: Ty) -> Self {
+        Ty::Fn { args, ret: Box::new(ret) }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ty::Param(name) | Ty::Lifetime(name) => f.write_str(name),
+            Ty::Path { name, args } => {
+                f.write_str(name)?;
+                write_args(f, args)
+            }
+            Ty::Ref { lifetime, mutable, ty } => {
+                f.write_str("&")?;
+                if let Some(lifetime) = lifetime {
+                    write!(f, "{} ", lifetime)?;
+                }
+                if *mutable {
+                    f.write_str("mut ")?;
+                }
+                write!(f, "{}", ty)
+            }
+            Ty::Ptr { mutable, ty } => {
+                write!(f, "*{} {}", if *mutable { "mut" } else { "const" }, ty)
+            }
+            Ty::Fn { args, ret } => {
+                f.write_str("fn(")?;
+                write_list(f, args)?;
+                f.write_str(")")?;
+                match &**ret {
+                    Ty::Tuple(elems) if elems.is_empty() => Ok(()),
+                    ret => write!(f, " -> {}", ret),
+                }
+            }
+            Ty::Dyn { name, args, lifetime } => {
+                write!(f, "dyn {}", name)?;
+                write_args(f, args)?;
+                match lifetime {
+                    Some(lifetime) => write!(f, " + {}", lifetime),
+                    None => Ok(()),
+                }
+            }
+            Ty::Tuple(elems) => {
+                f.write_str("(")?;
+                write_list(f, elems)?;
+                if elems.len() == 1 {
+                    f.write_str(",")?;
+                }
+                f.write_str(")")
+            }
+            Ty::Array(ty, len) => write!(f, "[{}; {}]", ty, len),
+            Ty::Slice(ty) => write!(f, "[{}]", ty),
+            Ty::Projection(ty, name) => match &**ty {
+                Ty::Param(_) => write!(f, "{}::{}", ty, name),
+                _ => write!(f, "<{}>::{}", ty, name),
+            },
+        }
+    }
+}
+
+fn write_list(f: &mut fmt::Formatter<'_>, tys: &[Ty]) -> fmt::Result {
+    for (i, ty) in tys.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", ty)?;
+    }
+    Ok(())
+}
+
+fn write_args(f: &mut fmt::Formatter<'_>, args: &[Ty]) -> fmt::Result {
+    if args.is_empty() {
+        return Ok(());
+    }
+    f.write_str("<")?;
+    write_list(f, args)?;
+    f.write_str(">")
+}
+
+/// A field of an [`Item`].
+///
+/// [`Item`]: struct.Item.html
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Field {
+    /// The name of the field, such as `value` or `0`. The fields of an enum
+    /// are named after their variant, as in `Some.0`.
+    pub name: String,
+    /// The type of the field.
+    pub ty: Ty,
+}
+
+impl Field {
+    /// Creates a field.
+    pub fn new(name: &str, ty: Ty) -> Self {
+        Self { name: name.to_owned(), ty }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.ty)
+    }
+}
+
+/// A struct, enum, or union definition.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Item {
+    /// The name of the type.
+    pub name: String,
+    /// The lifetime and type parameters, in order. Lifetimes are written with
+    /// their leading `'`.
+    pub params: Vec<String>,
+    /// The fields of the type, across all of its variants.
+    pub fields: Vec<Field>,
+}
+
+/// The result of [`infer`], with the variances of every item.
+///
+/// [`infer`]: fn.infer.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solution {
+    items: Vec<ItemVariance>,
+}
+
+impl Solution {
+    /// Returns the variances of each item, in the order they were given.
+    pub fn items(&self) -> &[ItemVariance] {
+        &self.items
+    }
+
+    /// Returns the variances of the item with the given name.
+    pub fn get(&self, item: &str) -> Option<&ItemVariance> {
+        self.items.iter().find(|it| it.name == item)
+    }
+
+    /// Explains the variance of a parameter of an item, following the causes
+    /// through the other items they depend on.
+    ///
+    /// The first line is the explanation of the parameter itself, and each
+    /// further line explains a parameter of another item which it depends on.
+    pub fn explain(&self, item: &str, param: &str) -> Option<String> {
+        let mut out = String::new();
+        let mut seen = BTreeSet::new();
+        let mut pending = Vec::new();
+        pending.push((item, param, 0));
+        while let Some((item, param, depth)) = pending.pop() {
+            let var = self.get(item)?.get(param)?;
+            if !seen.insert((item, param)) {
+                continue;
+            }
+            if depth > 0 {
+                out.push('\n');
+                for _ in 0..depth {
+                    out.push_str("  ");
+                }
+                out.push_str(&std::format!("where `{}` in `{}` is ", param, item));
+            } else {
+                out.push_str(&std::format!("`{}` in `{}` is ", param, item));
+            }
+            out.push_str(&var.to_string());
+            for cause in var.causes.iter().rev() {
+                if let Some((item, param)) = &cause.via {
+                    pending.push((item, param, depth + 1));
+                }
+            }
+        }
+        Some(out)
+    }
+}
+
+/// The variances of the parameters of an item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemVariance {
+    /// The name of the item.
+    pub name: String,
+    /// The variance of each parameter, in order.
+    pub params: Vec<ParamVariance>,
+}
+
+impl ItemVariance {
+    /// Returns the variance of the parameter with the given name.
+    pub fn get(&self, param: &str) -> Option<&ParamVariance> {
+        self.params.iter().find(|p| p.name == param)
+    }
+}
+
+/// The variance of a single parameter, with the fields that caused it.
+///
+/// Its [`Display`] impl explains the variance in a sentence, such as
+/// "invariant because of field `x: Cell<T>`".
+///
+/// [`Display`]: https://doc.rust-lang.org/stable/std/fmt/trait.Display.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamVariance {
+    /// The name of the parameter.
+    pub name: String,
+    /// The variance of the item with respect to the parameter.
+    pub kind: VarianceKind,
+    /// The uses of the parameter which determine its variance. This is empty
+    /// for a bivariant parameter, holds one use for a covariant or
+    /// contravariant parameter, and either one invariant use or a covariant
+    /// and a contravariant use for an invariant parameter.
+    pub causes: Vec<Cause>,
+}
+
+impl fmt::Display for ParamVariance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.kind.name())?;
+        if self.causes.is_empty() {
+            return f.write_str(" because it is unused");
+        }
+        f.write_str(" because of ")?;
+        for (i, cause) in self.causes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" and ")?;
+            }
+            write!(f, "{}", cause)?;
+        }
+        Ok(())
+    }
+}
+
+/// A use of a parameter within a field, and the variance it implies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cause {
+    /// The field which uses the parameter.
+    pub field: Field,
+    /// The variance implied by the use.
+    pub kind: VarianceKind,
+    /// The item and parameter through which the parameter is used, if it is
+    /// passed to another of the items. Their variance is part of the reason.
+    pub via: Option<(String, String)>,
+    /// The type which was assumed to be invariant, if the parameter is passed
+    /// to a type which is neither among the items nor well-known.
+    pub assumed: Option<String>,
+}
+
+impl fmt::Display for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field `{}`", self.field)?;
+        if let Some(name) = &self.assumed {
+            write!(f, " (assuming `{}` is invariant)", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// An error in the items given to [`infer`].
+///
+/// [`infer`]: fn.infer.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InferError {
+    /// Two items have the same name.
+    DuplicateItem(String),
+    /// A field uses a type parameter which the item does not declare.
+    UnknownParam {
+        /// The item with the field.
+        item: String,
+        /// The name of the parameter.
+        param: String,
+    },
+    /// A field passes the wrong number of arguments to another of the items.
+    ArgCount {
+        /// The item with the field.
+        item: String,
+        /// The type with the wrong number of arguments.
+        ty: String,
+        /// The number of parameters of the type.
+        expected: usize,
+        /// The number of arguments given.
+        found: usize,
+    },
+}
+
+impl fmt::Display for InferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InferError::DuplicateItem(name) => write!(f, "item `{}` is defined twice", name),
+            InferError::UnknownParam { item, param } => {
+                write!(f, "`{}` uses the undeclared parameter `{}`", item, param)
+            }
+            InferError::ArgCount { item, ty, expected, found } => write!(
+                f,
+                "`{}` uses `{}` with {} arguments, but it has {} parameters",
+                item, ty, found, expected,
+            ),
+        }
+    }
+}
+
+impl Error for InferError {}
+
+/// Infers the variance of every parameter of the given items.
+///
+/// See the [module documentation](index.html) for an example.
+pub fn infer(items: &[Item]) -> Result<Solution, InferError> {
+    let mut index = HashMap::new();
+    for (i, item) in items.iter().enumerate() {
+        if index.insert(item.name.as_str(), i).is_some() {
+            return Err(InferError::DuplicateItem(item.name.clone()));
+        }
+    }
+    let mut solver = Solver {
+        items,
+        index,
+        kinds: items.iter().map(|item| std::vec![Bivariant; item.params.len()]).collect(),
+    };
+    for (i, item) in items.iter().enumerate() {
+        for field in &item.fields {
+            solver.check(i, &field.ty)?;
+        }
+    }
+
+    // Every pass can only raise the variances in the lattice, which has a
+    // finite height, so this terminates.
+    loop {
+        let mut changed = false;
+        for (i, item) in items.iter().enumerate() {
+            let mut kinds = std::vec![Bivariant; item.params.len()];
+            for field in &item.fields {
+                solver.walk(i, &field.ty, Covariant, None, &mut |u| {
+                    kinds[u.param] = kinds[u.param].join(u.kind);
+                });
+            }
+            if kinds != solver.kinds[i] {
+                solver.kinds[i] = kinds;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut solution = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        let mut uses: Vec<Vec<Cause>> = std::vec![Vec::new(); item.params.len()];
+        for field in &item.fields {
+            solver.walk(i, &field.ty, Covariant, None, &mut |u| {
+                uses[u.param].push(Cause {
+                    field: field.clone(),
+                    kind: u.kind,
+                    via: u.via.map(|(item, param)| {
+                        (items[item].name.clone(), items[item].params[param].clone())
+                    }),
+                    assumed: u.assumed.map(ToOwned::to_owned),
+                });
+            });
+        }
+        let params = item.params.iter().zip(uses).enumerate().map(|(p, (name, uses))| {
+            let kind = solver.kinds[i][p];
+            ParamVariance { name: name.clone(), kind, causes: select_causes(kind, uses) }
+        });
+        solution.push(ItemVariance { name: item.name.clone(), params: params.collect() });
+    }
+    Ok(Solution { items: solution })
+}
+
+// Picks the uses which explain the variance of a parameter.
+fn select_causes(kind: VarianceKind, uses: Vec<Cause>) -> Vec<Cause> {
+    let first = |kind| uses.iter().find(|u| u.kind == kind).cloned();
+    match kind {
+        Bivariant => Vec::new(),
+        Covariant | Contravariant => first(kind).into_iter().collect(),
+        Invariant => match first(Invariant) {
+            Some(cause) => std::vec![cause],
+            None => first(Covariant).into_iter().chain(first(Contravariant)).collect(),
+        },
+    }
+}
+
+// A single use of a parameter, found while walking a field.
+struct Use<'a> {
+    param: usize,
+    kind: VarianceKind,
+    via: Option<(usize, usize)>,
+    assumed: Option<&'a str>,
+}
+
+struct Solver<'a> {
+    items: &'a [Item],
+    index: HashMap<&'a str, usize>,
+    kinds: Vec<Vec<VarianceKind>>,
+}
+
+impl<'a> Solver<'a> {
+    // Checks that every parameter used in `ty` is one of `item`, and that
+    // every other item is given the right number of arguments. This is done
+    // up front, since `walk` skips the types which are used bivariantly.
+    fn check(&self, item: usize, ty: &Ty) -> Result<(), InferError> {
+        match ty {
+            Ty::Param(name) => {
+                if self.param(item, name).is_none() {
+                    return Err(InferError::UnknownParam {
+                        item: self.items[item].name.clone(),
+                        param: name.clone(),
+                    });
+                }
+            }
+            Ty::Lifetime(_) => {}
+            Ty::Path { name, args } => {
+                if let Some(&other) = self.index.get(name.as_str()) {
+                    let expected = self.items[other].params.len();
+                    if args.len() != expected {
+                        return Err(InferError::ArgCount {
+                            item: self.items[item].name.clone(),
+                            ty: name.clone(),
+                            expected,
+                            found: args.len(),
+                        });
+                    }
+                }
+                for arg in args {
+                    self.check(item, arg)?;
+                }
+            }
+            Ty::Fn { args, ret } => {
+                for arg in args {
+                    self.check(item, arg)?;
+                }
+                self.check(item, ret)?;
+            }
+            Ty::Dyn { args, .. } | Ty::Tuple(args) => {
+                for arg in args {
+                    self.check(item, arg)?;
+                }
+            }
+            Ty::Ref { ty, .. }
+            | Ty::Ptr { ty, .. }
+            | Ty::Array(ty, _)
+            | Ty::Slice(ty)
+            | Ty::Projection(ty, _) => self.check(item, ty)?,
+        }
+        Ok(())
+    }
+
+    // Reports every use of a parameter of `item` within `ty`, which is itself
+    // used `ambient`-variantly. The type must have passed `check`.
+    fn walk(
+        &self,
+        item: usize,
+        ty: &'a Ty,
+        ambient: VarianceKind,
+        via: Option<(usize, usize)>,
+        out: &mut dyn FnMut(Use<'a>),
+    ) {
+        if ambient == Bivariant {
+            return;
+        }
+        match ty {
+            Ty::Param(name) => {
+                if let Some(param) = self.param(item, name) {
+                    out(Use { param, kind: ambient, via, assumed: None });
+                }
+            }
+            // Lifetimes which are not parameters are `'static`, `'_`, or bound
+            // by a higher-ranked type, and have no variance to report.
+            Ty::Lifetime(name) => self.lifetime(item, name, ambient, via, out),
+            Ty::Path { name, args } => {
+                if let Some(&other) = self.index.get(name.as_str()) {
+                    for (p, arg) in args.iter().enumerate() {
+                        let kind = ambient.compose(self.kinds[other][p]);
+                        self.walk(item, arg, kind, via.or(Some((other, p))), out);
+                    }
+                } else {
+                    let builtin = builtin(name);
+                    for (p, arg) in args.iter().enumerate() {
+                        let inner = match builtin {
+                            Some(kinds) => kinds.get(p).copied().unwrap_or(Invariant),
+                            None => Invariant,
+                        };
+                        let kind = ambient.compose(inner);
+                        match builtin {
+                            Some(_) => self.walk(item, arg, kind, via, out),
+                            None => self.walk(item, arg, kind, via, &mut |u: Use<'a>| {
+                                out(Use { assumed: u.assumed.or(Some(name.as_str())), ..u })
+                            }),
+                        }
+                    }
+                }
+            }
+            Ty::Ref { lifetime, mutable, ty } => {
+                if let Some(lifetime) = lifetime {
+                    self.lifetime(item, lifetime, ambient, via, out);
+                }
+                let inner = if *mutable { Invariant } else { Covariant };
+                self.walk(item, ty, ambient.compose(inner), via, out);
+            }
+            Ty::Ptr { mutable, ty } => {
+                let inner = if *mutable { Invariant } else { Covariant };
+                self.walk(item, ty, ambient.compose(inner), via, out);
+            }
+            Ty::Fn { args, ret } => {
+                for arg in args {
+                    self.walk(item, arg, ambient.compose(Contravariant), via, out);
+                }
+                self.walk(item, ret, ambient, via, out);
+            }
+            Ty::Dyn { args, lifetime, .. } => {
+                for arg in args {
+                    self.walk(item, arg, ambient.compose(Invariant), via, out);
+                }
+                if let Some(lifetime) = lifetime {
+                    self.lifetime(item, lifetime, ambient, via, out);
+                }
+            }
+            Ty::Tuple(elems) => {
+                for elem in elems {
+                    self.walk(item, elem, ambient, via, out);
+                }
+            }
+            Ty::Array(ty, _) | Ty::Slice(ty) => self.walk(item, ty, ambient, via, out),
+            Ty::Projection(ty, _) => self.walk(item, ty, ambient.compose(Invariant), via, out),
+        }
+    }
+
+    fn lifetime(
+        &self,
+        item: usize,
+        name: &str,
+        ambient: VarianceKind,
+        via: Option<(usize, usize)>,
+        out: &mut dyn FnMut(Use<'a>),
+    ) {
+        if let Some(param) = self.param(item, name) {
+            out(Use { param, kind: ambient, via, assumed: None });
+        }
+    }
+
+    fn param(&self, item: usize, name: &str) -> Option<usize> {
+        self.items[item].params.iter().position(|p| p == name)
+    }
+}
+
+// Returns the variance of a well-known type with respect to each of its
+// arguments, by the last segment of its path.
+//
+// NOTE: The `CheckVariance` derive keeps an identical table.
+fn builtin(path: &str) -> Option<&'static [VarianceKind]> {
+    const CO: &[VarianceKind] = &[Covariant; 4];
+    const CONTRA: &[VarianceKind] = &[Contravariant];
+    const INV: &[VarianceKind] = &[Invariant];
+
+    let name = path.rsplit("::").next().unwrap_or(path);
+    Some(match name {
+        "Cell" | "UnsafeCell" | "SyncUnsafeCell" | "RefCell" | "OnceCell" | "OnceLock"
+        | "Mutex" | "RwLock" | "AtomicPtr" | "Invariant" | "InvariantLifetime"
+        | "InvariantOwns" | "InvariantNonNull" | "InvariantBox" => INV,
+        "Contravariant" | "ContravariantLifetime" | "ContravariantNonNull" => CONTRA,
+        "BorrowsMut" | "MutexGuard" | "RwLockWriteGuard" | "RefMut" => &[Covariant, Invariant],
+        "FnMarker" => &[Contravariant, Covariant],
+        "Box" | "Vec" | "VecDeque" | "LinkedList" | "BinaryHeap" | "Rc" | "Arc" | "Weak"
+        | "Option" | "Result" | "NonNull" | "PhantomData" | "ManuallyDrop" | "MaybeUninit"
+        | "Pin" | "Reverse" | "Wrapping" | "Saturating" | "Cow" | "Ref"
+        | "HashMap" | "HashSet" | "BTreeMap" | "BTreeSet" | "Covariant"
+        | "CovariantLifetime" | "Lifetime" | "Owns" | "Borrows" | "HigherRanked"
+        | "Markers" => CO,
+        _ => return None,
+    })
+}
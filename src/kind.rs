@@ -0,0 +1,177 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::Variance;
+
+/// The variance of a type with respect to one of its parameters, as a value.
+///
+/// Every [`Variance`] marker exposes its kind as [`Variance::KIND`], which
+/// lets generic code inspect the variance that a marker encodes.
+///
+/// [`Variance`]: trait.Variance.html
+/// [`Variance::KIND`]: trait.Variance.html#associatedconstant.KIND
+///
+/// For example:
+/// ```
+/// use type_variance::{Contravariant, Variance, VarianceKind};
+///
+/// fn kind_of<V: Variance>(_: &V) -> VarianceKind {
+///     V::KIND
+/// }
+///
+/// assert_eq!(kind_of(&Contravariant::<u8>::new()), VarianceKind::Contravariant);
+/// ```
+///
+/// The kinds form a lattice, with `Bivariant` at the bottom and `Invariant` at
+/// the top, which is reflected by the [`PartialOrd`] impl. A kind which is
+/// less than another places fewer restrictions on subtyping. Covariance and
+/// contravariance are not comparable.
+///
+/// Kinds are written as `+`, `-`, `=`, and `*` by their [`Display`] and
+/// [`FromStr`] impls, for covariant, contravariant, invariant, and bivariant
+/// respectively:
+/// ```
+/// use type_variance::VarianceKind;
+///
+/// let kind: VarianceKind = "-".parse().unwrap();
+/// assert_eq!(kind.flip().to_string(), "+");
+/// assert!(VarianceKind::Covariant < VarianceKind::Invariant);
+/// ```
+///
+/// [`PartialOrd`]: https://doc.rust-lang.org/stable/std/cmp/trait.PartialOrd.html
+/// [`Display`]: https://doc.rust-lang.org/stable/std/fmt/trait.Display.html
+/// [`FromStr`]: https://doc.rust-lang.org/stable/std/str/trait.FromStr.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VarianceKind {
+    /// The parameter does not affect subtyping at all. This is the kind of
+    /// the markers which have no parameter, such as [`NotSend`].
+    ///
+    /// [`NotSend`]: struct.NotSend.html
+    Bivariant,
+    /// Subtyping of the parameter is preserved.
+    Covariant,
+    /// Subtyping of the parameter is reversed.
+    Contravariant,
+    /// The parameter must match exactly.
+    Invariant,
+}
+
+impl VarianceKind {
+    /// Returns the kind of the marker `V`.
+    pub const fn of<V: Variance>() -> Self {
+        V::KIND
+    }
+
+    /// Returns the opposite kind. Covariance and contravariance are swapped,
+    /// while invariance and bivariance are unchanged.
+    pub const fn flip(self) -> Self {
+        match self {
+            VarianceKind::Covariant => VarianceKind::Contravariant,
+            VarianceKind::Contravariant => VarianceKind::Covariant,
+            kind => kind,
+        }
+    }
+
+    /// Returns the variance of a parameter that is `inner`-variant within a
+    /// type which is used `self`-variantly.
+    ///
+    /// This is the value-level counterpart of [`Compose`].
+    ///
+    /// [`Compose`]: type.Compose.html
+    pub const fn compose(self, inner: Self) -> Self {
+        match (self, inner) {
+            (VarianceKind::Bivariant, _) | (_, VarianceKind::Bivariant) => {
+                VarianceKind::Bivariant
+            }
+            (VarianceKind::Covariant, inner) => inner,
+            (VarianceKind::Contravariant, inner) => inner.flip(),
+            (VarianceKind::Invariant, _) => VarianceKind::Invariant,
+        }
+    }
+
+    /// Returns the variance of a parameter that is used both `self`-variantly
+    /// and `other`-variantly. This is the least upper bound of the two kinds.
+    ///
+    /// This is the value-level counterpart of [`Join`].
+    ///
+    /// [`Join`]: type.Join.html
+    pub const fn join(self, other: Self) -> Self {
+        match (self, other) {
+            (VarianceKind::Bivariant, kind) | (kind, VarianceKind::Bivariant) => kind,
+            (VarianceKind::Covariant, VarianceKind::Covariant) => VarianceKind::Covariant,
+            (VarianceKind::Contravariant, VarianceKind::Contravariant) => {
+                VarianceKind::Contravariant
+            }
+            _ => VarianceKind::Invariant,
+        }
+    }
+
+    /// Returns the notation for this kind, as used by the `Display` impl.
+    pub const fn symbol(self) -> char {
+        match self {
+            VarianceKind::Bivariant => '*',
+            VarianceKind::Covariant => '+',
+            VarianceKind::Contravariant => '-',
+            VarianceKind::Invariant => '=',
+        }
+    }
+
+    /// Returns the name of this kind in lowercase, such as `"covariant"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            VarianceKind::Bivariant => "bivariant",
+            VarianceKind::Covariant => "covariant",
+            VarianceKind::Contravariant => "contravariant",
+            VarianceKind::Invariant => "invariant",
+        }
+    }
+}
+
+impl PartialOrd for VarianceKind {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            Some(Ordering::Equal)
+        } else if self.join(*other) == *other {
+            Some(Ordering::Less)
+        } else if self.join(*other) == *self {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for VarianceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.symbol(), f)
+    }
+}
+
+impl FromStr for VarianceKind {
+    type Err = ParseVarianceKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "*" => Ok(VarianceKind::Bivariant),
+            "+" => Ok(VarianceKind::Covariant),
+            "-" => Ok(VarianceKind::Contravariant),
+            "=" => Ok(VarianceKind::Invariant),
+            _ => Err(ParseVarianceKindError { _priv: () }),
+        }
+    }
+}
+
+/// The error returned when parsing a [`VarianceKind`] fails.
+///
+/// [`VarianceKind`]: enum.VarianceKind.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseVarianceKindError {
+    _priv: (),
+}
+
+impl fmt::Display for ParseVarianceKindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid variance, expected one of `+`, `-`, `=`, or `*`")
+    }
+}
@@ -1,4 +1,5 @@
 #![no_std]
+#![cfg_attr(feature = "nightly", feature(coerce_unsized, dispatch_from_dyn, unsize))]
 
 //! Marker types to indicate precisely the [variance] relationship between a
 //! generic type and its parameters.
@@ -30,11 +31,28 @@
 //! ```
 //! use type_variance::{Covariant, Contravariant};
 //!
+//! // Has the same variance as `fn(Arg) -> Ret`.
 //! struct Func<Arg, Ret> {
-//!     arg: Covariant<Arg>,
-//!     ret: Contravariant<Ret>,
+//!     arg: Contravariant<Arg>,
+//!     ret: Covariant<Ret>,
 //! }
 //! ```
+//! For function-shaped types such as this one, [`FnMarker`] mirrors the
+//! variance of a function pointer with any number of arguments directly, and
+//! a `Covariant<for<'a> fn(&'a T) -> &'a U>` that of a higher-ranked one, as
+//! described for the [`HigherRanked`] alias.
+//!
+//! Like a `PhantomData`, each of these markers is a value of its own type, and
+//! can be written as such in struct literals and patterns:
+//! ```
+//! # use type_variance::{Covariant, Contravariant};
+//! # struct Func<Arg, Ret> {
+//! #     arg: Contravariant<Arg>,
+//! #     ret: Covariant<Ret>,
+//! # }
+//! let func: Func<u8, u16> = Func { arg: Contravariant, ret: Covariant };
+//! let Func { arg: Contravariant, ret: Covariant } = func;
+//! ```
 //!
 //! ## Enforcing invariance
 //!
@@ -57,8 +75,169 @@
 //!
 //! Like `PhantomData`, the provided variance markers only accept type
 //! parameters. To indicate a generic type's variance with respect to its
-//! lifetime parameters, use the [`Lifetime`] wrapper, which converts a
-//! lifetime to a regular type while preserving its subtyping relation.
+//! lifetime parameters, use one of [`CovariantLifetime`],
+//! [`ContravariantLifetime`], and [`InvariantLifetime`].
+//! ```
+//! use type_variance::InvariantLifetime;
+//!
+//! struct Arena<'id> {
+//!     marker: InvariantLifetime<'id>,
+//! }
+//! ```
+//! These are shorthands for wrapping the [`Lifetime`] type, which converts a
+//! lifetime to a regular type while preserving its subtyping relation, in the
+//! corresponding type parameter marker.
+//!
+//! ## Checking variance
+//!
+//! The [`assert_covariant!`] and [`assert_contravariant!`] macros check the
+//! variance of a type at compile time, so that a refactor which silently
+//! changes it breaks the build. The parameter under test is written as `_`, or
+//! as `'_` for a lifetime:
+//! ```
+//! use type_variance::{assert_covariant, Covariant};
+//!
+//! struct Producer<T> {
+//!     marker: Covariant<T>,
+//! }
+//!
+//! assert_covariant!(Producer<_>);
+//! ```
+//! More general relations can be checked with [`assert_subtype!`].
+//!
+//! A successful coercion can only show that a type is _at least_ as permissive
+//! as expected. The [`variance_suite!`] macro additionally generates
+//! `compile_fail` doctests for each forbidden coercion, so that a type which
+//! is unexpectedly covariant or contravariant is also caught.
+//!
+//! For the same reason, there is no `assert_invariant!`. Invariance is the
+//! absence of any coercion, which code that compiles cannot detect, and trait
+//! selection cannot observe subtyping either. An invariant type is instead
+//! checked by an `invariant` entry of [`variance_suite!`], which is run by
+//! `cargo test` along with the other doctests:
+//! ```
+//! #[cfg(doctest)]
+//! type_variance::variance_suite! {
+//!     mod variance_tests {
+//!         invariant(type_variance::Invariant<_>);
+//!     }
+//! }
+//! ```
+//!
+//! ## Ownership and borrowing
+//!
+//! The markers above do not imply that the type _owns_ a value of type `T`:
+//! `Covariant<T>` is built on `PhantomData<fn() -> T>`, which neither
+//! participates in drop checking nor propagates the auto traits of `T`. For
+//! types that own their `T`s through raw pointers, such as collections, use
+//! [`Owns<T>`] or [`InvariantOwns<T>`] instead. Similarly, types that borrow
+//! through raw pointers, such as cursors and views, can use
+//! [`Borrows<'a, T>`] or [`BorrowsMut<'a, T>`] to get the variance and
+//! implied `T: 'a` bound of the corresponding reference. Whether a marker
+//! drops a `T` only matters to a `Drop` impl using the unstable
+//! `#[may_dangle]` attribute, as with `PhantomData<T>`:
+//!
+//! | Marker               | Variance in `T` | Drops a `T` | `Send`/`Sync`     |
+//! |----------------------|-----------------|-------------|-------------------|
+//! | `Covariant<T>`       | covariant       | no          | always            |
+//! | `Contravariant<T>`   | contravariant   | no          | always            |
+//! | `Invariant<T>`       | invariant       | no          | always            |
+//! | `Owns<T>`            | covariant       | yes         | when `T` is       |
+//! | `InvariantOwns<T>`   | invariant       | yes         | when `T` is       |
+//! | `Borrows<'a, T>`     | covariant       | no          | as for `&'a T`    |
+//! | `BorrowsMut<'a, T>`  | invariant       | no          | as for `&'a mut T`|
+//!
+//! ```
+//! use type_variance::Owns;
+//!
+//! struct Slice<T> {
+//!     start: *mut T,
+//!     end: *mut T,
+//!     marker: Owns<T>,
+//! }
+//! ```
+//!
+//! ## Raw pointers
+//!
+//! [`NonNull<T>`] is covariant, which is rarely what mutable linked
+//! structures want. Rather than pairing it with an `Invariant<T>` marker by
+//! hand, the [`InvariantNonNull<T>`] and [`ContravariantNonNull<T>`] wrappers
+//! provide the same interface with the stated variance:
+//! ```
+//! use type_variance::InvariantNonNull;
+//!
+//! struct Link<T> {
+//!     value: T,
+//!     prev: Option<InvariantNonNull<Link<T>>>,
+//!     next: Option<InvariantNonNull<Link<T>>>,
+//! }
+//! ```
+//! With the `alloc` feature enabled, [`InvariantBox<T>`] provides the same for
+//! `Box<T>`.
+//!
+//! With the `nightly` feature enabled on a nightly compiler, both wrappers
+//! also take part in unsizing coercions, so that an `InvariantNonNull<[T; N]>`
+//! coerces to an `InvariantNonNull<[T]>` just as `NonNull` does. These two
+//! wrappers are the only types which implement `CoerceUnsized`.
+//!
+//! The markers themselves cannot be coerced, since the compiler only allows
+//! unsizing coercions of pointer types. In particular, a `Handle<[u8; 4]>`
+//! whose only use of its parameter is a marker does not coerce to a
+//! `Handle<[u8]>`, with or without the feature. Instead, each marker with a
+//! type parameter gets an `unsize` method, with which such a struct can be
+//! converted by hand.
+//!
+//! ## Auto traits
+//!
+//! The variance markers are always [`Send`], [`Sync`], [`Unpin`], and
+//! [`UnwindSafe`], regardless of their type parameter. Types which need to opt
+//! out of these traits, such as FFI handles, can combine a variance marker
+//! with [`NotSend`], [`NotSync`], [`NotUnpin`], or [`NotUnwindSafe`]:
+//! ```
+//! use type_variance::{Invariant, NotSend, NotSync};
+//!
+//! struct Handle<T> {
+//!     raw: *mut u8,
+//!     variance: Invariant<T>,
+//!     auto_traits: (NotSend, NotSync),
+//! }
+//! ```
+//! Each of these is zero-sized and only removes the one trait it is named
+//! after. Note that they must be used as separate fields: a type such as
+//! `Covariant<NotSend>` is still `Send`.
+//!
+//! ## Many parameters
+//!
+//! Rather than declaring a marker field per parameter, the markers for all of
+//! them can be bundled into one field with [`Markers`], or the shorthand
+//! [`markers!`] macro, and built with a single call to [`variance`]:
+//! ```
+//! use type_variance::{markers, variance};
+//!
+//! struct Pipeline<'a, In, Out, State> {
+//!     stages: Vec<*mut u8>,
+//!     marker: markers![+'a, -In, +Out, =State],
+//! }
+//!
+//! let pipeline: Pipeline<u8, u16, u32> = Pipeline {
+//!     stages: Vec::new(),
+//!     marker: variance(),
+//! };
+//! ```
+//! The [`variance_struct!`] macro goes one step further, reading the variance
+//! of each parameter from the declaration itself and generating a `new`
+//! constructor which fills in the markers:
+//! ```
+//! use type_variance::variance_struct;
+//!
+//! variance_struct! {
+//!     struct Pipeline<+'a, -In, +Out, =State> {
+//!         stages: Vec<*mut u8>,
+//!     }
+//! }
+//!
+//! let pipeline: Pipeline<u8, u16, u32> = Pipeline::new(Vec::new());
+//! ```
 //!
 //! # Limitations
 //!
@@ -81,80 +260,418 @@
 //!
 //! Due to this, it is recommended that `Covariant` and `Contravariant` are only
 //! used on type parameters that are not used in any other fields of the type.
+//! Where this cannot be avoided, the [`Join`] alias names the resulting marker,
+//! and [`Compose`] does the same for a parameter nested in another variant
+//! type. With the `macros` feature enabled, deriving
+//! [`CheckVariance`](derive.CheckVariance.html) turns such conflicts into
+//! compile errors.
+//!
+//! # Attribute macro
+//!
+//! With the `macros` feature enabled, the [`variance`](attr.variance.html)
+//! attribute declares the variance of each parameter of a struct and injects
+//! the corresponding marker field:
+//! ```
+//! # #[cfg(feature = "macros")] {
+//! use type_variance::variance;
+//!
+//! #[variance(Arg = contravariant, Ret = covariant)]
+//! struct Func<Arg, Ret> {
+//!     data: u32,
+//! }
+//!
+//! let func: Func<u8, u16> = Func { data: 42, _variance: variance() };
+//! # }
+//! ```
+//!
+//! # Variance inference
+//!
+//! With the `std` feature enabled, the [`infer`](infer/index.html) module
+//! computes the variance of type definitions outside of the compiler, along
+//! with the fields which explain each result. This is intended for tooling
+//! and reviews, where the compiler's own answer is not at hand.
 //!
 //! [variance]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
 //! [1]: https://doc.rust-lang.org/nomicon/subtyping.html#variance
 //! [`PhantomData`]: https://doc.rust-lang.org/stable/std/marker/struct.PhantomData.html
-//! [`Covariant`]: struct.Covariant.html
-//! [`Contravariant`]: struct.Contravariant.html
-//! [`Invariant`]: struct.Invariant.html
+//! [`Covariant`]: enum.Covariant.html
+//! [`Contravariant`]: enum.Contravariant.html
+//! [`Invariant`]: enum.Invariant.html
 //! [`Lifetime`]: struct.Lifetime.html
+//! [`CovariantLifetime`]: enum.CovariantLifetime.html
+//! [`ContravariantLifetime`]: enum.ContravariantLifetime.html
+//! [`InvariantLifetime`]: enum.InvariantLifetime.html
+//! [`Send`]: https://doc.rust-lang.org/stable/std/marker/trait.Send.html
+//! [`Sync`]: https://doc.rust-lang.org/stable/std/marker/trait.Sync.html
+//! [`Unpin`]: https://doc.rust-lang.org/stable/std/marker/trait.Unpin.html
+//! [`UnwindSafe`]: https://doc.rust-lang.org/stable/std/panic/trait.UnwindSafe.html
+//! [`NotSend`]: struct.NotSend.html
+//! [`NotSync`]: struct.NotSync.html
+//! [`NotUnpin`]: struct.NotUnpin.html
+//! [`NotUnwindSafe`]: struct.NotUnwindSafe.html
+//! [`Owns<T>`]: struct.Owns.html
+//! [`InvariantOwns<T>`]: struct.InvariantOwns.html
+//! [`Borrows<'a, T>`]: struct.Borrows.html
+//! [`BorrowsMut<'a, T>`]: struct.BorrowsMut.html
+//! [`NonNull<T>`]: https://doc.rust-lang.org/stable/std/ptr/struct.NonNull.html
+//! [`InvariantNonNull<T>`]: struct.InvariantNonNull.html
+//! [`ContravariantNonNull<T>`]: struct.ContravariantNonNull.html
+//! [`InvariantBox<T>`]: struct.InvariantBox.html
+//! [`assert_covariant!`]: macro.assert_covariant.html
+//! [`assert_contravariant!`]: macro.assert_contravariant.html
+//! [`assert_subtype!`]: macro.assert_subtype.html
+//! [`variance_suite!`]: macro.variance_suite.html
+//! [`Join`]: type.Join.html
+//! [`Markers`]: struct.Markers.html
+//! [`FnMarker`]: struct.FnMarker.html
+//! [`HigherRanked`]: type.HigherRanked.html
+//! [`markers!`]: macro.markers.html
+//! [`variance_struct!`]: macro.variance_struct.html
+//! [`variance`]: fn.variance.html
+//! [`Compose`]: type.Compose.html
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::any::type_name;
 use core::marker::PhantomData;
 
+#[cfg(feature = "macros")]
+pub use type_variance_macros::{variance, CheckVariance};
+
+// Implements the standard traits for a zero-sized marker type. Unlike a
+// #[derive], this does not add any bounds on the marker's type parameters, so
+// that e.g. `Covariant<String>` is `Copy` and `Covariant<dyn Trait>` is
+// `Clone`. The remaining arguments are used to format the `Debug` output.
+macro_rules! impl_marker_traits {
+    ([$($gen:tt)*] $ty:ty, $($fmt:tt)+) => {
+        impl<$($gen)*> Clone for $ty {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<$($gen)*> Copy for $ty {}
+
+        impl<$($gen)*> PartialEq for $ty {
+            fn eq(&self, _: &Self) -> bool {
+                true
+            }
+        }
+
+        impl<$($gen)*> Eq for $ty {}
+
+        impl<$($gen)*> PartialOrd for $ty {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<$($gen)*> Ord for $ty {
+            fn cmp(&self, _: &Self) -> core::cmp::Ordering {
+                core::cmp::Ordering::Equal
+            }
+        }
+
+        impl<$($gen)*> core::hash::Hash for $ty {
+            fn hash<H: core::hash::Hasher>(&self, _: &mut H) {}
+        }
+
+        impl<$($gen)*> core::fmt::Debug for $ty {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, $($fmt)+)
+            }
+        }
+    };
+}
+
+// Implements the `const` constructors for a zero-sized marker type, given an
+// expression which builds it. An `of_val` constructor is also generated when
+// given the type of its argument.
+macro_rules! impl_marker_new {
+    ([$($gen:tt)*] $ty:ty, $value:expr $(, of_val($arg:ty))?) => {
+        impl<$($gen)*> $ty {
+            /// The marker value, for use in `const` and `static` initializers.
+            pub const MARKER: Self = $value;
+
+            /// Creates the marker. This is equivalent to `Default::default()`,
+            /// but may be called in `const` contexts.
+            pub const fn new() -> Self {
+                Self::MARKER
+            }
+
+            $(
+                /// Creates the marker, inferring its type parameter from a
+                /// reference to a value.
+                pub const fn of_val(_: $arg) -> Self {
+                    Self::MARKER
+                }
+            )?
+        }
+    };
+}
+
+// Implements `unsize` for a marker type with a type parameter, behind the
+// `nightly` feature.
+macro_rules! impl_marker_unsize {
+    ($name:ident $(<$lt:lifetime>)?) => {
+        #[cfg(feature = "nightly")]
+        impl<$($lt,)? T: ?Sized> $name<$($lt,)? T> {
+            /// Converts the marker for `T` into the marker for the unsized type
+            /// `U`, such as from `[T; N]` to `[T]`, or from `T` to `dyn Trait`.
+            ///
+            /// The compiler only allows unsizing coercions of pointer types, so
+            /// a struct holding the marker must use this to rebuild its marker
+            /// when it is converted by hand.
+            pub const fn unsize<U: ?Sized>(self) -> $name<$($lt,)? U>
+            where
+                T: core::marker::Unsize<U>,
+            {
+                $name::MARKER
+            }
+        }
+    };
+}
+
+// Implements the conversions between a marker type and the `PhantomData` it
+// is equivalent to.
+macro_rules! impl_phantom_conversions {
+    ([$($gen:tt)*] $ty:ty, $phantom:ty) => {
+        impl<$($gen)*> From<$phantom> for $ty {
+            fn from(_: $phantom) -> Self {
+                Self::MARKER
+            }
+        }
+
+        impl<$($gen)*> From<$ty> for $phantom {
+            fn from(_: $ty) -> Self {
+                PhantomData
+            }
+        }
+    };
+}
+
+#[macro_use]
+mod assert;
+mod algebra;
+mod auto_traits;
+#[macro_use]
+mod bundle;
+#[macro_use]
+mod declare;
+mod func;
+#[cfg(feature = "std")]
+pub mod infer;
+mod kind;
+mod ownership;
+mod ptr;
+mod tag;
+#[macro_use]
+mod trusted;
+
+pub use algebra::{Compose, ComposeWith, Join, JoinWith};
+pub use auto_traits::{NotSend, NotSync, NotUnpin, NotUnwindSafe};
+pub use bundle::Markers;
+pub use func::{FnArgs, FnMarker, HigherRanked};
+pub use kind::{ParseVarianceKindError, VarianceKind};
+pub use ownership::{Borrows, BorrowsMut, InvariantOwns, Owns};
+pub use ptr::{ContravariantNonNull, InvariantNonNull};
+pub use tag::{Co, Contra, Inv, Phantom, VarianceTag};
+pub use trusted::TrustedVariance;
+#[cfg(feature = "alloc")]
+pub use ptr::InvariantBox;
+
 /// A sealed trait implemented by `Covariant<T>`, `Contravariant<T>`, and
-/// `Invariant<T>`.
-pub trait Variance: Default + private::Sealed {}
+/// `Invariant<T>`, as well as their lifetime counterparts, the ownership
+/// markers, [`FnMarker`], and the auto trait markers. Tuples of markers and [`Markers`]
+/// bundles are also markers. Marker types from other crates may implement
+/// [`TrustedVariance`] to become markers as well.
+///
+/// [`FnMarker`]: struct.FnMarker.html
+/// [`Markers`]: struct.Markers.html
+/// [`TrustedVariance`]: trait.TrustedVariance.html
+///
+/// The associated items describe the marker at the type level, so that code
+/// which is generic over a marker can name its parameter, flip it, or apply
+/// the same variance to another type.
+///
+/// For example:
+/// ```
+/// use type_variance::{Contravariant, Covariant, Variance, VarianceKind};
+///
+/// // A pair of markers with opposite variance over the same parameter.
+/// struct Both<V: Variance> {
+///     forward: V,
+///     backward: V::Flipped,
+/// }
+///
+/// let both: Both<Covariant<u8>> = Both {
+///     forward: Covariant::new(),
+///     backward: Contravariant::new(),
+/// };
+/// assert_eq!(<Covariant<u8> as Variance>::KIND, VarianceKind::Covariant);
+/// ```
+pub trait Variance: Default + private::Sealed {
+    /// The parameter that the marker has a variance with respect to. This is
+    /// `Lifetime<'a>` for the lifetime markers, and `()` for markers without
+    /// a parameter.
+    type Param: ?Sized;
+
+    /// The marker with the opposite variance over the same parameter.
+    /// Covariant and contravariant markers are swapped, while invariant and
+    /// bivariant markers are left unchanged.
+    ///
+    /// Only the variance is carried over. Nothing can own or borrow a `T`
+    /// contravariantly, so the flipped marker of [`Owns<T>`] and
+    /// [`Borrows<'a, T>`] is the plain `Contravariant<T>`, which neither drops
+    /// a `T` nor borrows it for `'a`.
+    ///
+    /// [`Owns<T>`]: struct.Owns.html
+    /// [`Borrows<'a, T>`]: struct.Borrows.html
+    type Flipped: Variance;
+
+    /// A marker with the same variance, applied to the parameter `U` instead.
+    ///
+    /// The ownership markers keep their ownership, but the borrowing markers
+    /// lose their lifetime, since `U: 'a` is not known to hold:
+    /// `Borrows<'a, T>` and `BorrowsMut<'a, T>` fall back to `Covariant<U>`
+    /// and `Invariant<U>` respectively.
+    type WithParam<U: ?Sized>: Variance;
+
+    /// The variance of the marker with respect to `Param`.
+    const KIND: VarianceKind;
+}
 
 /// Zero-sized type used to mark a type as [covariant] with respect to its type
 /// parameter `T`.
 ///
 /// [covariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
 ///
+/// Like a `PhantomData`, the marker is a value of its own type, which can be
+/// written as `Covariant` or `Covariant::<T>` in both expressions and
+/// patterns:
+/// ```
+/// use type_variance::Covariant;
+///
+/// struct Producer<T> {
+///     id: u32,
+///     marker: Covariant<T>,
+/// }
+///
+/// let producer = Producer::<u8> { id: 1, marker: Covariant };
+/// let Producer { id, marker: Covariant } = producer;
+/// ```
+///
 /// See the [module-level documentation](index.html) for more.
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
-pub struct Covariant<T: ?Sized> {
-    marker: PhantomData<fn() -> T>,
+pub enum Covariant<T: ?Sized> {
+    /// The marker value.
+    Covariant,
+    #[doc(hidden)]
+    __CovariantPhantom(private::Void, PhantomData<fn() -> T>),
 }
 
+#[doc(hidden)]
+pub use self::Covariant::*;
+
 /// Zero-sized type used to mark a type as [contravariant] with respect to its type
 /// parameter `T`.
 ///
 /// [contravariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
 ///
+/// The marker value is written as `Contravariant`, as for [`Covariant`].
+///
+/// [`Covariant`]: enum.Covariant.html
+///
 /// See the [module-level documentation](index.html) for more.
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
-pub struct Contravariant<T: ?Sized> {
-    marker: PhantomData<fn(T)>,
+pub enum Contravariant<T: ?Sized> {
+    /// The marker value.
+    Contravariant,
+    #[doc(hidden)]
+    __ContravariantPhantom(private::Void, PhantomData<fn(T)>),
 }
 
+#[doc(hidden)]
+pub use self::Contravariant::*;
+
 /// Zero-sized type used to mark a type as [invariant] with respect to its type
 /// parameter `T`.
 ///
 /// [invariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
 ///
+/// The marker value is written as `Invariant`, as for [`Covariant`].
+///
+/// [`Covariant`]: enum.Covariant.html
+///
 /// See the [module-level documentation](index.html) for more.
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
-pub struct Invariant<T: ?Sized> {
-    marker: (Covariant<T>, Contravariant<T>),
+pub enum Invariant<T: ?Sized> {
+    /// The marker value.
+    Invariant,
+    #[doc(hidden)]
+    __InvariantPhantom(private::Void, PhantomData<fn(T) -> T>),
 }
 
+#[doc(hidden)]
+pub use self::Invariant::*;
+
 // NOTE: These manual impls are necessary due to the following issue, but
 // can be replaced with a #[derive(Default)] when/if it gets resolved.
 // https://github.com/rust-lang/rust/issues/26925
 impl<T: ?Sized> Default for Covariant<T> {
     fn default() -> Self {
-        Self { marker: Default::default(), }
+        Self::MARKER
     }
 }
 impl<T: ?Sized> Default for Contravariant<T> {
     fn default() -> Self {
-        Self { marker: Default::default(), }
+        Self::MARKER
     }
 }
 impl<T: ?Sized> Default for Invariant<T> {
     fn default() -> Self {
-        Self { marker: Default::default(), }
+        Self::MARKER
     }
 }
 
+impl_marker_new!([T: ?Sized] Covariant<T>, Self::Covariant, of_val(&T));
+impl_marker_new!([T: ?Sized] Contravariant<T>, Self::Contravariant, of_val(&T));
+impl_marker_new!([T: ?Sized] Invariant<T>, Self::Invariant, of_val(&T));
+
+impl_marker_unsize!(Covariant);
+impl_marker_unsize!(Contravariant);
+impl_marker_unsize!(Invariant);
+
+impl_phantom_conversions!([T: ?Sized] Covariant<T>, PhantomData<fn() -> T>);
+impl_phantom_conversions!([T: ?Sized] Contravariant<T>, PhantomData<fn(T)>);
+impl_phantom_conversions!([T: ?Sized] Invariant<T>, PhantomData<fn(T) -> T>);
+
+impl_marker_traits!([T: ?Sized] Covariant<T>, "Covariant<{}>", type_name::<T>());
+impl_marker_traits!([T: ?Sized] Contravariant<T>, "Contravariant<{}>", type_name::<T>());
+impl_marker_traits!([T: ?Sized] Invariant<T>, "Invariant<{}>", type_name::<T>());
+
 impl<T: ?Sized> private::Sealed for Covariant<T> {}
 impl<T: ?Sized> private::Sealed for Contravariant<T> {}
 impl<T: ?Sized> private::Sealed for Invariant<T> {}
 
-impl<T: ?Sized> Variance for Covariant<T> {}
-impl<T: ?Sized> Variance for Contravariant<T> {}
-impl<T: ?Sized> Variance for Invariant<T> {}
+impl<T: ?Sized> Variance for Covariant<T> {
+    type Param = T;
+    type Flipped = Contravariant<T>;
+    type WithParam<U: ?Sized> = Covariant<U>;
+    const KIND: VarianceKind = VarianceKind::Covariant;
+}
+impl<T: ?Sized> Variance for Contravariant<T> {
+    type Param = T;
+    type Flipped = Covariant<T>;
+    type WithParam<U: ?Sized> = Contravariant<U>;
+    const KIND: VarianceKind = VarianceKind::Contravariant;
+}
+impl<T: ?Sized> Variance for Invariant<T> {
+    type Param = T;
+    type Flipped = Invariant<T>;
+    type WithParam<U: ?Sized> = Invariant<U>;
+    const KIND: VarianceKind = VarianceKind::Invariant;
+}
 
 /// Variance-preserving type wrapper around a lifetime parameter.
 ///
@@ -173,13 +690,135 @@ impl<T: ?Sized> Variance for Invariant<T> {}
 ///
 /// Note that this type is not constructible, and so should be wrapped with
 /// either a `PhantomData` or one of the variance marker types.
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub struct Lifetime<'a> {
     _variance: Covariant<&'a ()>,
 }
 
+/// Zero-sized type used to mark a type as [covariant] with respect to its
+/// lifetime parameter `'a`.
+///
+/// This is equivalent to `Covariant<Lifetime<'a>>`.
+///
+/// [covariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+///
+/// For example:
+/// ```
+/// use type_variance::CovariantLifetime;
+///
+/// struct Guard<'a> {
+///     handle: *const u8,
+///     marker: CovariantLifetime<'a>,
+/// }
+/// ```
+#[derive(Default)]
+pub enum CovariantLifetime<'a> {
+    /// The marker value.
+    #[default]
+    CovariantLifetime,
+    #[doc(hidden)]
+    __CovariantLifetimePhantom(private::Void, Covariant<Lifetime<'a>>),
+}
+
+#[doc(hidden)]
+pub use self::CovariantLifetime::*;
+
+/// Zero-sized type used to mark a type as [contravariant] with respect to its
+/// lifetime parameter `'a`.
+///
+/// This is equivalent to `Contravariant<Lifetime<'a>>`.
+///
+/// [contravariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+#[derive(Default)]
+pub enum ContravariantLifetime<'a> {
+    /// The marker value.
+    #[default]
+    ContravariantLifetime,
+    #[doc(hidden)]
+    __ContravariantLifetimePhantom(private::Void, Contravariant<Lifetime<'a>>),
+}
+
+#[doc(hidden)]
+pub use self::ContravariantLifetime::*;
+
+/// Zero-sized type used to mark a type as [invariant] with respect to its
+/// lifetime parameter `'a`.
+///
+/// This is equivalent to `Invariant<Lifetime<'a>>`. An invariant lifetime
+/// can be used as a unique "brand" that cannot be unified with any other
+/// lifetime.
+///
+/// [invariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+#[derive(Default)]
+pub enum InvariantLifetime<'a> {
+    /// The marker value.
+    #[default]
+    InvariantLifetime,
+    #[doc(hidden)]
+    __InvariantLifetimePhantom(private::Void, Invariant<Lifetime<'a>>),
+}
+
+#[doc(hidden)]
+pub use self::InvariantLifetime::*;
+
+impl_marker_new!(['a] CovariantLifetime<'a>, Self::CovariantLifetime);
+impl_marker_new!(['a] ContravariantLifetime<'a>, Self::ContravariantLifetime);
+impl_marker_new!(['a] InvariantLifetime<'a>, Self::InvariantLifetime);
+
+impl_marker_traits!(['a] Lifetime<'a>, "Lifetime<'_>");
+impl_marker_traits!(['a] CovariantLifetime<'a>, "CovariantLifetime<'_>");
+impl_marker_traits!(['a] ContravariantLifetime<'a>, "ContravariantLifetime<'_>");
+impl_marker_traits!(['a] InvariantLifetime<'a>, "InvariantLifetime<'_>");
+
+impl private::Sealed for CovariantLifetime<'_> {}
+impl private::Sealed for ContravariantLifetime<'_> {}
+impl private::Sealed for InvariantLifetime<'_> {}
+
+impl<'a> Variance for CovariantLifetime<'a> {
+    type Param = Lifetime<'a>;
+    type Flipped = ContravariantLifetime<'a>;
+    type WithParam<U: ?Sized> = Covariant<U>;
+    const KIND: VarianceKind = VarianceKind::Covariant;
+}
+impl<'a> Variance for ContravariantLifetime<'a> {
+    type Param = Lifetime<'a>;
+    type Flipped = CovariantLifetime<'a>;
+    type WithParam<U: ?Sized> = Contravariant<U>;
+    const KIND: VarianceKind = VarianceKind::Contravariant;
+}
+impl<'a> Variance for InvariantLifetime<'a> {
+    type Param = Lifetime<'a>;
+    type Flipped = InvariantLifetime<'a>;
+    type WithParam<U: ?Sized> = Invariant<U>;
+    const KIND: VarianceKind = VarianceKind::Invariant;
+}
+
+/// A trait implemented by every type, used to declare that a return-position
+/// `impl Trait` type captures the lifetime `'a`.
+///
+/// Prior to the 2024 edition, an `impl Trait` return type only captures the
+/// lifetimes that appear in its bounds. Adding `Captures<'a>` as a bound allows
+/// the hidden type to borrow from `'a` without otherwise constraining it.
+///
+/// For example:
+/// ```
+/// use type_variance::Captures;
+///
+/// fn zip<'a, 'b>(a: &'a [u8], b: &'b [u8])
+///     -> impl Iterator<Item = (u8, u8)> + Captures<'a> + Captures<'b>
+/// {
+///     a.iter().copied().zip(b.iter().copied())
+/// }
+/// ```
+pub trait Captures<'a> {}
+
+impl<T: ?Sized> Captures<'_> for T {}
+
 /// A convenience function for constructing any of `Covariant<T>`,
-/// `Contravariant<T>`, and `Invariant<T>`. It is equivalent to [`default`].
+/// `Contravariant<T>`, `Invariant<T>`, the lifetime markers, and bundles of
+/// several markers. It is equivalent to [`default`].
+///
+/// Since trait methods cannot be called in `const` contexts, use the `new`
+/// constructor or the `MARKER` constant of the marker type there instead.
 ///
 /// [`default`]: https://doc.rust-lang.org/stable/std/default/trait.Default.html#tymethod.default
 ///
@@ -205,14 +844,53 @@ pub fn variance<T: Variance>() -> T {
     Default::default()
 }
 
+#[cfg(doctest)]
+variance_suite! {
+    mod variance_suite {
+        covariant(type_variance::Covariant<_>);
+        contravariant(type_variance::Contravariant<_>);
+        invariant(type_variance::Invariant<_>);
+        covariant(type_variance::Covariant<type_variance::Lifetime<'_>>);
+        covariant(type_variance::CovariantLifetime<'_>);
+        contravariant(type_variance::ContravariantLifetime<'_>);
+        invariant(type_variance::InvariantLifetime<'_>);
+        covariant(type_variance::Owns<_>);
+        invariant(type_variance::InvariantOwns<_>);
+        covariant(type_variance::Borrows<'_, u8>);
+        covariant(type_variance::Borrows<'_, _>);
+        covariant(type_variance::BorrowsMut<'_, u8>);
+        invariant(type_variance::BorrowsMut<'_, _>);
+        invariant(type_variance::InvariantNonNull<_>);
+        contravariant(type_variance::ContravariantNonNull<_>);
+        covariant(type_variance::Phantom<type_variance::Co, _>);
+        contravariant(type_variance::Phantom<type_variance::Contra, _>);
+        invariant(type_variance::Phantom<type_variance::Inv, _>);
+        invariant(type_variance::Join<type_variance::Covariant<_>, type_variance::Contravariant<_>>);
+    }
+}
+
+#[cfg(all(doctest, feature = "alloc"))]
+variance_suite! {
+    mod alloc_variance_suite {
+        invariant(type_variance::InvariantBox<_>);
+    }
+}
+
 // Prevent external implementations of `Variance`.
 mod private {
     pub trait Sealed {}
+
+    // The type of the hidden field which makes the phantom variant of each
+    // marker impossible to construct.
+    #[derive(Clone, Copy)]
+    pub enum Void {}
 }
 
 #[cfg(test)]
+#[allow(clippy::extra_unused_lifetimes)]
 mod tests {
-    use super::{Covariant, Contravariant, Lifetime};
+    use super::{Covariant, Contravariant, Invariant, Lifetime};
+    use super::{CovariantLifetime, ContravariantLifetime, InvariantLifetime};
 
     struct Co<X>(Covariant<X>);
     struct Contra<X>(Contravariant<X>);
@@ -234,12 +912,585 @@ mod tests {
     #[test]
     fn co_contra<'a>() {
         struct Func<Arg, Ret> {
-            _arg: Covariant<Arg>,
-            _ret: Contravariant<Ret>,
+            _arg: Contravariant<Arg>,
+            _ret: Covariant<Ret>,
+        }
+        let _func: Func<Lifetime<'static>, Lifetime<'a>> = Func {
+            _arg: Contravariant::<Lifetime<'a>>::default(),
+            _ret: Covariant::<Lifetime<'static>>::default(),
+        };
+    }
+
+    assert_covariant!(Covariant<_>);
+    assert_covariant!(Covariant<Lifetime<'_>>);
+    assert_contravariant!(Contravariant<_>);
+    assert_contravariant!(Contravariant<Lifetime<'_>>);
+    assert_subtype!(<'a> Co<Lifetime<'static>>, Co<Lifetime<'a>>);
+    assert_subtype!(<'a> Contra<Lifetime<'a>>, Contra<Lifetime<'static>>);
+
+    assert_covariant!(CovariantLifetime<'_>);
+    assert_contravariant!(ContravariantLifetime<'_>);
+
+    #[test]
+    fn bound_free_impls() {
+        extern crate std;
+        use std::format;
+        use std::string::String;
+
+        fn copy<T: Copy + Ord + core::hash::Hash + core::fmt::Debug>() {}
+
+        copy::<Covariant<String>>();
+        copy::<Contravariant<[String]>>();
+        copy::<Invariant<dyn core::any::Any>>();
+        copy::<CovariantLifetime<'static>>();
+        copy::<super::Owns<str>>();
+        copy::<super::BorrowsMut<'static, String>>();
+        copy::<super::NotSend>();
+
+        assert_eq!(
+            format!("{:?}", Covariant::<String>::default()),
+            "Covariant<alloc::string::String>",
+        );
+        assert_eq!(format!("{:?}", Invariant::<[u8]>::default()), "Invariant<[u8]>");
+        assert_eq!(
+            format!("{:?}", super::Borrows::<'static, u8>::default()),
+            "Borrows<'_, u8>",
+        );
+        assert_eq!(format!("{:?}", InvariantLifetime::default()), "InvariantLifetime<'_>");
+        assert_eq!(format!("{:?}", super::NotSync::default()), "NotSync");
+        assert_eq!(Contravariant::<str>::default(), Contravariant::<str>::default());
+    }
+
+    #[test]
+    fn auto_traits() {
+        use core::panic::{RefUnwindSafe, UnwindSafe};
+        use super::{NotSend, NotSync, NotUnpin, NotUnwindSafe};
+
+        fn send<T: Send>() {}
+        fn sync<T: Sync>() {}
+        fn unpin<T: Unpin>() {}
+        fn unwind_safe<T: UnwindSafe>() {}
+        fn ref_unwind_safe<T: RefUnwindSafe>() {}
+
+        sync::<NotSend>();
+        unpin::<NotSend>();
+        unwind_safe::<NotSend>();
+        ref_unwind_safe::<NotSend>();
+        send::<NotSync>();
+        unpin::<NotSync>();
+        unwind_safe::<NotSync>();
+        ref_unwind_safe::<NotSync>();
+        send::<NotUnpin>();
+        sync::<NotUnpin>();
+        unwind_safe::<NotUnpin>();
+        ref_unwind_safe::<NotUnpin>();
+        send::<NotUnwindSafe>();
+        sync::<NotUnwindSafe>();
+        unpin::<NotUnwindSafe>();
+        ref_unwind_safe::<NotUnwindSafe>();
+        assert_eq!(core::mem::size_of::<(NotSend, NotSync, NotUnpin, NotUnwindSafe)>(), 0);
+    }
+
+    assert_covariant!(super::Owns<_>);
+    assert_covariant!(super::Borrows<'_, u8>);
+    assert_covariant!(super::Borrows<'_, _>);
+    assert_covariant!(super::BorrowsMut<'_, u8>);
+
+    #[test]
+    fn ownership() {
+        use super::{Borrows, BorrowsMut, InvariantOwns, Owns};
+
+        fn send_sync<T: Send + Sync>() {}
+
+        send_sync::<Owns<u8>>();
+        send_sync::<InvariantOwns<str>>();
+        send_sync::<Borrows<'static, [u8]>>();
+        send_sync::<BorrowsMut<'static, u8>>();
+        assert_eq!(core::mem::size_of::<(Owns<u64>, InvariantOwns<u64>)>(), 0);
+        assert_eq!(core::mem::size_of::<(Borrows<u64>, BorrowsMut<u64>)>(), 0);
+    }
+
+    #[test]
+    fn const_constructors() {
+        use core::marker::PhantomData;
+        use super::{Borrows, NotSend, Owns};
+
+        const INV: Invariant<str> = Invariant::new();
+        static CO: Covariant<dyn Fn()> = Covariant::MARKER;
+        static LT: InvariantLifetime<'static> = InvariantLifetime::MARKER;
+        const NOT_SEND: NotSend = NotSend::new();
+
+        assert_eq!(INV, Invariant::default());
+        assert_eq!(CO, Covariant::default());
+        assert_eq!(LT, InvariantLifetime::default());
+        assert_eq!(NOT_SEND, NotSend::default());
+
+        let value = [1u8, 2, 3];
+        let _: Covariant<[u8; 3]> = Covariant::of_val(&value);
+        let _: Contravariant<[u8]> = Contravariant::of_val(&value[..]);
+        let _: Owns<str> = Owns::of_val("owned");
+        let _: Borrows<'_, [u8]> = Borrows::of_val(&value[..]);
+
+        let co: PhantomData<fn() -> u8> = Covariant::<u8>::new().into();
+        let contra: PhantomData<fn(u8)> = Contravariant::<u8>::new().into();
+        let inv: PhantomData<fn(u8) -> u8> = Invariant::<u8>::new().into();
+        assert_eq!(Covariant::from(co), Covariant::<u8>::MARKER);
+        assert_eq!(Contravariant::from(contra), Contravariant::<u8>::MARKER);
+        assert_eq!(Invariant::from(inv), Invariant::<u8>::MARKER);
+        assert_eq!(Owns::from(PhantomData::<u8>), Owns::<u8>::MARKER);
+    }
+
+    #[test]
+    fn associated_items() {
+        use core::marker::PhantomData;
+        use super::{BorrowsMut, NotSync, Owns, Variance, VarianceKind};
+
+        fn kind<V: Variance>() -> VarianceKind {
+            V::KIND
+        }
+        fn flip<V: Variance>(_: V) -> V::Flipped {
+            Default::default()
+        }
+        fn rebind<V: Variance, U: ?Sized>(_: V) -> V::WithParam<U> {
+            Default::default()
+        }
+
+        assert_eq!(kind::<Covariant<str>>(), VarianceKind::Covariant);
+        assert_eq!(kind::<ContravariantLifetime>(), VarianceKind::Contravariant);
+        assert_eq!(kind::<BorrowsMut<u8>>(), VarianceKind::Invariant);
+        assert_eq!(kind::<NotSync>(), VarianceKind::Bivariant);
+
+        let _: Contravariant<u8> = flip(Covariant::<u8>::new());
+        let _: Covariant<u8> = flip(flip(Covariant::<u8>::new()));
+        let _: Invariant<u8> = flip(Invariant::<u8>::new());
+        let _: CovariantLifetime = flip(ContravariantLifetime::new());
+        let _: NotSync = flip(NotSync::new());
+
+        let _: Owns<[u8]> = rebind::<_, [u8]>(Owns::<u8>::new());
+        let _: Contravariant<u16> = rebind::<_, u16>(Contravariant::<u8>::new());
+
+        fn same<T: ?Sized>(_: PhantomData<T>, _: PhantomData<T>) {}
+        same(PhantomData::<<Owns<str> as Variance>::Param>, PhantomData::<str>);
+        same(
+            PhantomData::<<InvariantLifetime<'static> as Variance>::Param>,
+            PhantomData::<Lifetime<'static>>,
+        );
+        same(PhantomData::<<NotSync as Variance>::Param>, PhantomData::<()>);
+    }
+
+    assert_covariant!(super::Compose<Contravariant<()>, Contravariant<_>>);
+    assert_contravariant!(super::Compose<Covariant<()>, Contravariant<_>>);
+
+    #[test]
+    fn algebra() {
+        use core::marker::PhantomData;
+        use super::{BorrowsMut, Compose, Join, Owns};
+
+        fn same<T: ?Sized>(_: PhantomData<T>, _: PhantomData<T>) {}
+        fn compose<A: super::ComposeWith<B>, B: super::Variance>() -> PhantomData<Compose<A, B>> {
+            PhantomData
+        }
+        fn join<A: super::JoinWith<B>, B: super::Variance>() -> PhantomData<Join<A, B>> {
+            PhantomData
+        }
+
+        same(compose::<Covariant<()>, Contravariant<u8>>(), PhantomData::<Contravariant<u8>>);
+        same(compose::<Contravariant<()>, Contravariant<u8>>(), PhantomData::<Covariant<u8>>);
+        same(compose::<Contravariant<()>, Owns<str>>(), PhantomData::<Contravariant<str>>);
+        same(compose::<Invariant<()>, Covariant<u8>>(), PhantomData::<Invariant<u8>>);
+        same(compose::<Owns<()>, InvariantLifetime>(), PhantomData::<Invariant<Lifetime>>);
+
+        same(join::<Covariant<u8>, Owns<u8>>(), PhantomData::<Covariant<u8>>);
+        same(join::<Contravariant<u8>, Contravariant<u8>>(), PhantomData::<Contravariant<u8>>);
+        same(join::<Covariant<u8>, Contravariant<u8>>(), PhantomData::<Invariant<u8>>);
+        same(join::<Contravariant<u8>, BorrowsMut<u8>>(), PhantomData::<Invariant<u8>>);
+        same(
+            join::<CovariantLifetime, ContravariantLifetime>(),
+            PhantomData::<Invariant<Lifetime>>,
+        );
+    }
+
+    #[test]
+    fn variance_kind() {
+        extern crate std;
+        use std::string::ToString;
+        use super::{Compose, Join, Variance, VarianceKind};
+        use super::VarianceKind::{Bivariant, Contravariant as Contra, Covariant as Co, Invariant as Inv};
+
+        const KIND: VarianceKind = VarianceKind::of::<Invariant<u8>>().compose(Contra);
+        assert_eq!(KIND, Inv);
+
+        assert_eq!(Co.compose(Contra), Contra);
+        assert_eq!(Contra.compose(Contra), Co);
+        assert_eq!(Inv.compose(Bivariant), Bivariant);
+        assert_eq!(Bivariant.join(Contra), Contra);
+        assert_eq!(Co.join(Contra), Inv);
+        assert_eq!(Inv.flip(), Inv);
+
+        assert!(Bivariant < Co && Co < Inv && Contra <= Inv);
+        assert_eq!(Co.partial_cmp(&Contra), None);
+
+        for kind in [Bivariant, Co, Contra, Inv] {
+            assert_eq!(kind.to_string().parse::<VarianceKind>(), Ok(kind));
+        }
+        assert!("co".parse::<VarianceKind>().is_err());
+
+        // The value-level operators agree with the type-level ones.
+        macro_rules! check {
+            ($($a:ident $b:ident),*) => {$(
+                assert_eq!(
+                    <Compose<$a<u8>, $b<u8>> as Variance>::KIND,
+                    <$a<u8>>::KIND.compose(<$b<u8>>::KIND),
+                );
+                assert_eq!(
+                    <Join<$a<u8>, $b<u8>> as Variance>::KIND,
+                    <$a<u8>>::KIND.join(<$b<u8>>::KIND),
+                );
+            )*};
+        }
+        check!(
+            Covariant Covariant, Covariant Contravariant, Covariant Invariant,
+            Contravariant Covariant, Contravariant Contravariant, Contravariant Invariant,
+            Invariant Covariant, Invariant Contravariant, Invariant Invariant
+        );
+    }
+
+    assert_contravariant!(super::ContravariantNonNull<_>);
+
+    assert_covariant!(super::Phantom<super::Co, _>);
+    assert_contravariant!(super::Phantom<super::Contra, _>);
+
+    #[test]
+    fn unit_values<'a>() {
+        use core::mem::size_of;
+
+        struct Func<'id, Arg, Ret> {
+            arg: Contravariant<Arg>,
+            ret: Covariant<Ret>,
+            id: InvariantLifetime<'id>,
         }
-        let _func: Func<Lifetime<'a>, Lifetime<'static>> = Func {
-            _arg: Covariant::<Lifetime<'static>>::default(),
-            _ret: Contravariant::<Lifetime<'a>>::default(),
+
+        let func: Func<Lifetime<'static>, Lifetime<'a>> = Func {
+            arg: Contravariant::<Lifetime<'a>>,
+            ret: Covariant::<Lifetime<'static>>,
+            id: InvariantLifetime,
+        };
+        let Func { arg: Contravariant, ret: Covariant, id: InvariantLifetime } = func;
+        match Invariant::<str>::MARKER {
+            Invariant => {}
+        }
+        assert_eq!(Covariant::<u8>, Covariant::default());
+        assert_eq!(CovariantLifetime, CovariantLifetime::new());
+
+        assert_eq!(size_of::<Invariant<[u64]>>(), 0);
+        assert_eq!(size_of::<ContravariantLifetime>(), 0);
+        assert_eq!(size_of::<Option<Covariant<u64>>>(), 1);
+    }
+
+    #[test]
+    fn higher_ranked<'a>() {
+        use super::{Covariant, HigherRanked};
+
+        let _arg: Covariant<fn(&'static u8)> = Covariant::<for<'b> fn(&'b u8)>::new();
+        let _sig: Covariant<fn(&'a str) -> &'a u8> =
+            Covariant::<for<'b> fn(&'b str) -> &'b u8>::new();
+        let _ret: Covariant<for<'b> fn(&'b str) -> &'a u8> =
+            Covariant::<for<'b> fn(&'b str) -> &'static u8>::new();
+        let _alias: HigherRanked<fn(&'a u8)> = HigherRanked::<for<'b> fn(&'b u8)>::new();
+    }
+
+    assert_subtype!(
+        <'a> super::Covariant<for<'b> fn(&'b u8) -> &'b u8>,
+        super::Covariant<fn(&'a u8) -> &'a u8>,
+    );
+
+    type Callback<A, B, R> = super::FnMarker<(A, B), R>;
+
+    assert_contravariant!(Callback<_, u8, u8>);
+    assert_contravariant!(Callback<u8, _, u8>);
+    assert_covariant!(Callback<u8, u8, _>);
+    assert_contravariant!(super::FnMarker<_>);
+
+    #[test]
+    fn fn_marker() {
+        use core::marker::PhantomData;
+        use super::FnMarker;
+
+        type Twelve = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+
+        let _: PhantomData<fn()> = FnMarker::<()>::new().into();
+        let _: PhantomData<fn(&'static str) -> u8> =
+            FnMarker::<(&'static str,), u8>::new().into();
+        let _: FnMarker<Twelve, bool> =
+            PhantomData::<fn(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8) -> bool>.into();
+        assert_eq!(core::mem::size_of::<FnMarker<Twelve, [u8; 16]>>(), 0);
+        assert_eq!(FnMarker::<(u8,), str>::MARKER, FnMarker::default());
+    }
+
+    #[test]
+    fn fn_marker_variance() {
+        use super::{variance, Covariant, FnMarker, Markers, Variance, VarianceKind};
+
+        type Callback = FnMarker<(u8,), bool>;
+
+        let _: Callback = variance();
+        let _: <Callback as Variance>::Flipped = Covariant::<(u8,)>::new();
+        let _: <Callback as Variance>::WithParam<(u16,)> = FnMarker::<(u16,), bool>::new();
+        assert_eq!(<Callback as Variance>::KIND, VarianceKind::Contravariant);
+        let _: Markers<(Callback, Covariant<u16>)> = variance();
+    }
+
+    type CoBundle<'a, T> = markers![+'a, +T, -u8];
+    type ContraBundle<T> = markers![=u8, -T, +'static];
+
+    assert_covariant!(CoBundle<'static, _>);
+    assert_covariant!(CoBundle<'_, u8>);
+    assert_contravariant!(ContraBundle<_>);
+
+    #[test]
+    fn bundles() {
+        use super::{variance, Markers, NotSend, Owns, Variance, VarianceKind};
+
+        let bundle: markers![+'static, -u8, =str] = variance();
+        let _: (CovariantLifetime, Contravariant<u8>, Invariant<str>) = bundle.into_inner();
+        let _: Markers<()> = <markers![]>::new();
+        let _: Markers<(Covariant<u8>,)> = (Covariant::new(),).into();
+
+        assert_eq!(<markers![]>::KIND, VarianceKind::Bivariant);
+        assert_eq!(<markers![+u8, +u16,]>::KIND, VarianceKind::Covariant);
+        assert_eq!(<(NotSend, Contravariant<u8>)>::KIND, VarianceKind::Contravariant);
+        assert_eq!(<markers![+u8, -u16]>::KIND, VarianceKind::Invariant);
+        assert_eq!(core::mem::size_of::<markers![+u8, -u8, =u8, +'static]>(), 0);
+
+        let _: markers![-u8, +u16] = <markers![+u8, -u16] as Variance>::Flipped::new();
+        let _: (Owns<u8>, Invariant<u8>) =
+            <(Owns<()>, Invariant<()>) as Variance>::WithParam::<u8>::default();
+    }
+
+    variance_struct! {
+        #[derive(Debug, PartialEq)]
+        pub struct Declared<+'a, -Arg, +Ret, =State> {
+            pub name: &'a str,
+            len: usize,
+        }
+    }
+
+    variance_struct! {
+        struct Unsized<=T> where T: ?Sized {}
+    }
+
+    variance_struct! {
+        struct Sink<-T,> {
+            len: usize,
+        }
+    }
+
+    assert_covariant!(Declared<'_, (), (), ()>);
+    assert_contravariant!(Declared<'static, _, (), ()>);
+    assert_covariant!(Declared<'static, (), _, ()>);
+    assert_contravariant!(Sink<_>);
+
+    #[test]
+    fn declared_structs() {
+        let declared: Declared<u8, u16, u32> = Declared::new("len", 3);
+        assert_eq!(declared, Declared::new("len", 3));
+        assert_eq!((declared.name, declared.len), ("len", 3));
+        assert_eq!(
+            core::mem::size_of::<Declared<u8, u16, u32>>(),
+            core::mem::size_of::<(&str, usize)>(),
+        );
+        let _: Unsized<str> = Unsized::new();
+
+        const SINK: Sink<u8> = Sink::new(4);
+        assert_eq!(SINK.len, 4);
+    }
+
+    #[test]
+    fn pointers() {
+        use core::mem::size_of;
+        use core::ptr::NonNull;
+        use super::{ContravariantNonNull, InvariantNonNull};
+
+        assert_eq!(size_of::<Option<InvariantNonNull<u8>>>(), size_of::<*mut u8>());
+        assert_eq!(size_of::<Option<InvariantNonNull<[u8]>>>(), size_of::<*mut [u8]>());
+        assert_eq!(size_of::<Option<ContravariantNonNull<u8>>>(), size_of::<*mut u8>());
+
+        let mut value = [1u16, 2];
+        let mut inv = InvariantNonNull::<[u16]>::from(&mut value[..]);
+        unsafe { inv.as_mut()[0] = 3; }
+        let bytes = InvariantNonNull::new(value.as_mut_ptr()).unwrap().cast::<u8>();
+        assert_eq!(bytes.as_ptr(), value.as_mut_ptr().cast());
+
+        let contra = ContravariantNonNull::from(NonNull::from(&mut value[1]));
+        unsafe { *contra.clone().as_mut() += 1; }
+        assert_eq!(unsafe { *contra.as_ref() }, 3);
+        assert_eq!(value, [3, 3]);
+        assert_eq!(inv, InvariantNonNull::from(&mut value[..]));
+        assert!(ContravariantNonNull::<u8>::new(core::ptr::null_mut()).is_none());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn invariant_box() {
+        use alloc::boxed::Box;
+        use super::InvariantBox;
+
+        let mut boxed = InvariantBox::<[i32]>::from(Box::new([1, 2, 3]) as Box<[i32]>);
+        boxed[0] = 4;
+        assert_eq!(&*boxed, &[4, 2, 3]);
+        assert_eq!(InvariantBox::into_box(boxed.clone()).len(), 3);
+        assert_eq!(InvariantBox::into_inner(InvariantBox::new(5)), 5);
+
+        let text = InvariantBox::<str>::from(Box::<str>::from("text"));
+        assert_eq!(text.clone(), text);
+        assert!(text < InvariantBox::from(Box::<str>::from("tree")));
+        assert_eq!(&*InvariantBox::<[u8]>::default(), &[]);
+        assert!(boxed > InvariantBox::from(Box::new([1, 2]) as Box<[i32]>));
+    }
+
+    struct Opaque<T: ?Sized>(core::marker::PhantomData<fn(T)>);
+
+    impl<T: ?Sized> Default for Opaque<T> {
+        fn default() -> Self {
+            Opaque(Default::default())
+        }
+    }
+
+    #[derive(Default)]
+    struct Region<'a>(core::marker::PhantomData<fn(&'a ()) -> &'a ()>);
+
+    impl_variance! {
+        Opaque<T: ?Sized>: contravariant;
+        // SAFETY: `'a` appears in both an argument and the return type.
+        unsafe Region<'a>: invariant;
+    }
+
+    #[test]
+    fn trusted_variance() {
+        use super::{variance, Compose, Join, Variance, VarianceKind};
+
+        let _: Opaque<str> = variance();
+        let _: Covariant<u8> = <Opaque<u8> as Variance>::Flipped::default();
+        let _: Contravariant<u16> = <Opaque<u8> as Variance>::WithParam::<u16>::default();
+        let _: Invariant<u8> = Join::<Opaque<u8>, Covariant<u8>>::default();
+        let _: Covariant<u8> = Compose::<Opaque<()>, Opaque<u8>>::default();
+        assert_eq!(<Opaque<u8> as Variance>::KIND, VarianceKind::Contravariant);
+        assert_eq!(<Region<'static> as Variance>::KIND, VarianceKind::Invariant);
+        let _: Invariant<Lifetime<'static>> = <Region<'static> as Variance>::Flipped::default();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn infer() {
+        use std::string::ToString;
+        use std::vec::Vec;
+        use std::vec;
+        use super::infer::{infer, Field, InferError, Item, Ty};
+        use super::VarianceKind::{self, *};
+
+        let item = |name: &str, params: &[&str], fields| Item {
+            name: name.into(),
+            params: params.iter().map(|&p| p.into()).collect(),
+            fields,
         };
+        let t = || Ty::param("T");
+
+        let items = [
+            // struct List<'a, T> { head: Option<Box<Node<'a, T>>> }
+            item("List", &["'a", "T"], vec![Field::new(
+                "head",
+                Ty::path("Option", vec![Ty::path("Box", vec![
+                    Ty::path("Node", vec![Ty::lifetime("'a"), t()]),
+                ])]),
+            )]),
+            // struct Node<'a, T> { value: &'a T, next: Option<Box<Node<'a, T>>> }
+            item("Node", &["'a", "T"], vec![
+                Field::new("value", Ty::reference(Some("'a"), false, t())),
+                Field::new("next", Ty::path("Option", vec![Ty::path("Box", vec![
+                    Ty::path("Node", vec![Ty::lifetime("'a"), t()]),
+                ])])),
+            ]),
+            // struct Sink<T, U> { f: fn(T) -> U, raw: *mut U, obj: Box<dyn Fn(T)> }
+            item("Sink", &["T", "U", "V"], vec![
+                Field::new("f", Ty::function(vec![t()], Ty::param("U"))),
+                Field::new("raw", Ty::pointer(true, Ty::param("U"))),
+                Field::new("obj", Ty::path("Box", vec![Ty::Dyn {
+                    name: "Fn".into(),
+                    args: vec![Ty::Tuple(vec![t()])],
+                    lifetime: None,
+                }])),
+            ]),
+            // struct Pair<T> { sink: Sink<T, u8, T>, get: fn() -> T }
+            item("Pair", &["T"], vec![
+                Field::new("sink", Ty::path("Sink", vec![t(), Ty::path("u8", vec![]), t()])),
+                Field::new("get", Ty::function(vec![], t())),
+            ]),
+            // struct Mixed<T> { get: fn() -> T, set: fn(T), other: Foreign<T> }
+            item("Mixed", &["T", "U"], vec![
+                Field::new("get", Ty::function(vec![], t())),
+                Field::new("set", Ty::function(vec![t()], Ty::Tuple(vec![]))),
+                Field::new("other", Ty::path("Foreign", vec![Ty::param("U")])),
+            ]),
+            // struct Shared<T> { inner: Pair<Cell<T>> }
+            item("Shared", &["T"], vec![Field::new(
+                "inner",
+                Ty::path("Pair", vec![Ty::path("std::cell::Cell", vec![t()])]),
+            )]),
+        ];
+        let solution = infer(&items).unwrap();
+        let kinds = |name| -> Vec<VarianceKind> {
+            solution.get(name).unwrap().params.iter().map(|p| p.kind).collect()
+        };
+
+        assert_eq!(kinds("List"), [Covariant, Covariant]);
+        assert_eq!(kinds("Sink"), [Invariant, Invariant, Bivariant]);
+        assert_eq!(kinds("Pair"), [Invariant]);
+        assert_eq!(kinds("Mixed"), [Invariant, Invariant]);
+
+        let mixed = solution.get("Mixed").unwrap();
+        assert_eq!(
+            mixed.get("T").unwrap().to_string(),
+            "invariant because of field `get: fn() -> T` and field `set: fn(T)`",
+        );
+        assert_eq!(
+            mixed.get("U").unwrap().to_string(),
+            "invariant because of field `other: Foreign<U>` (assuming `Foreign` is invariant)",
+        );
+        assert_eq!(
+            solution.get("Sink").unwrap().get("V").unwrap().to_string(),
+            "bivariant because it is unused",
+        );
+        assert_eq!(
+            solution.explain("Shared", "T").unwrap(),
+            "`T` in `Shared` is invariant because of field `inner: Pair<std::cell::Cell<T>>`\n\
+             \x20 where `T` in `Pair` is invariant because of field `sink: Sink<T, u8, T>`\n\
+             \x20   where `T` in `Sink` is invariant because of field `obj: Box<dyn Fn<(T,)>>`",
+        );
+
+        let unknown = item("Bad", &[], vec![Field::new("x", t())]);
+        assert_eq!(
+            infer(&[unknown]),
+            Err(InferError::UnknownParam { item: "Bad".into(), param: "T".into() }),
+        );
+
+        // Both are caught even where the argument is used bivariantly.
+        let bivariant = item("Bad", &[], vec![Field::new(
+            "sink",
+            Ty::path("Sink", vec![Ty::path("u8", vec![]), Ty::path("u8", vec![]), t()]),
+        )]);
+        assert_eq!(
+            infer(&[items[2].clone(), bivariant]),
+            Err(InferError::UnknownParam { item: "Bad".into(), param: "T".into() }),
+        );
+        let count = item("Bad", &[], vec![Field::new("sink", Ty::path("Sink", vec![]))]);
+        assert_eq!(
+            infer(&[items[2].clone(), count]),
+            Err(InferError::ArgCount {
+                item: "Bad".into(),
+                ty: "Sink".into(),
+                expected: 3,
+                found: 0,
+            }),
+        );
     }
 }
@@ -0,0 +1,189 @@
+use core::any::type_name;
+use core::marker::PhantomData;
+
+use crate::{private, Contravariant, Covariant, Invariant, Variance, VarianceKind};
+
+/// Zero-sized type used to mark a type as owning values of type `T`, and
+/// being [covariant] with respect to `T`.
+///
+/// Unlike [`Covariant<T>`], this tells the drop checker that dropping the
+/// type may drop a `T`, and makes the type [`Send`] and [`Sync`] only when `T`
+/// is. It behaves exactly like a `PhantomData<T>`.
+///
+/// As with `PhantomData<T>`, the drop checker only takes this into account
+/// for a `Drop` impl which marks `T` with the unstable `#[may_dangle]`
+/// attribute. Without one, dropping the type is already assumed to access
+/// its `T`s, and the marker only affects the auto traits.
+///
+/// [covariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+/// [`Covariant<T>`]: enum.Covariant.html
+/// [`Send`]: https://doc.rust-lang.org/stable/std/marker/trait.Send.html
+/// [`Sync`]: https://doc.rust-lang.org/stable/std/marker/trait.Sync.html
+///
+/// For example:
+/// ```
+/// use type_variance::Owns;
+///
+/// struct RawVec<T> {
+///     ptr: *mut T,
+///     len: usize,
+///     marker: Owns<T>,
+/// }
+/// ```
+pub struct Owns<T: ?Sized> {
+    marker: PhantomData<T>,
+}
+
+/// Zero-sized type used to mark a type as owning values of type `T`, and
+/// being [invariant] with respect to `T`.
+///
+/// Like [`Owns<T>`], this tells the drop checker that dropping the type may
+/// drop a `T`, which matters for a `Drop` impl using `#[may_dangle]`, and
+/// propagates the auto traits of `T`.
+///
+/// [invariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+/// [`Owns<T>`]: struct.Owns.html
+///
+/// For example:
+/// ```
+/// use type_variance::InvariantOwns;
+///
+/// struct Node<T> {
+///     value: *mut T,
+///     next: *mut Node<T>,
+///     marker: InvariantOwns<T>,
+/// }
+/// ```
+pub struct InvariantOwns<T: ?Sized> {
+    owns: PhantomData<T>,
+    _variance: Invariant<T>,
+}
+
+/// Zero-sized type used to mark a type as borrowing values of type `T` for
+/// the lifetime `'a`, with the variance of `&'a T`.
+///
+/// The type is [covariant] with respect to both `'a` and `T`, and implies the
+/// bound `T: 'a`. Like a shared reference, it is [`Send`] and [`Sync`] only
+/// when `T` is `Sync`. This is useful for cursors and views over raw pointers.
+///
+/// [covariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+/// [`Send`]: https://doc.rust-lang.org/stable/std/marker/trait.Send.html
+/// [`Sync`]: https://doc.rust-lang.org/stable/std/marker/trait.Sync.html
+///
+/// For example:
+/// ```
+/// use type_variance::Borrows;
+///
+/// struct Iter<'a, T> {
+///     ptr: *const T,
+///     end: *const T,
+///     marker: Borrows<'a, T>,
+/// }
+/// ```
+pub struct Borrows<'a, T: ?Sized + 'a> {
+    marker: PhantomData<&'a T>,
+}
+
+/// Zero-sized type used to mark a type as mutably borrowing values of type
+/// `T` for the lifetime `'a`, with the variance of `&'a mut T`.
+///
+/// The type is [covariant] with respect to `'a` but [invariant] with respect
+/// to `T`, and implies the bound `T: 'a`. Like a mutable reference, it is
+/// [`Send`] only when `T` is `Send`, and [`Sync`] only when `T` is `Sync`.
+///
+/// [covariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+/// [invariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+/// [`Send`]: https://doc.rust-lang.org/stable/std/marker/trait.Send.html
+/// [`Sync`]: https://doc.rust-lang.org/stable/std/marker/trait.Sync.html
+///
+/// For example:
+/// ```
+/// use type_variance::BorrowsMut;
+///
+/// struct IterMut<'a, T> {
+///     ptr: *mut T,
+///     end: *mut T,
+///     marker: BorrowsMut<'a, T>,
+/// }
+/// ```
+pub struct BorrowsMut<'a, T: ?Sized + 'a> {
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<T: ?Sized> Default for Owns<T> {
+    fn default() -> Self {
+        Self::MARKER
+    }
+}
+impl<T: ?Sized> Default for InvariantOwns<T> {
+    fn default() -> Self {
+        Self::MARKER
+    }
+}
+impl<T: ?Sized> Default for Borrows<'_, T> {
+    fn default() -> Self {
+        Self::MARKER
+    }
+}
+impl<T: ?Sized> Default for BorrowsMut<'_, T> {
+    fn default() -> Self {
+        Self::MARKER
+    }
+}
+
+impl_marker_new!([T: ?Sized] Owns<T>, Self { marker: PhantomData }, of_val(&T));
+impl_marker_new!(
+    [T: ?Sized] InvariantOwns<T>,
+    Self { owns: PhantomData, _variance: Invariant::MARKER },
+    of_val(&T)
+);
+impl_marker_new!(['a, T: ?Sized] Borrows<'a, T>, Self { marker: PhantomData }, of_val(&'a T));
+impl_marker_new!(['a, T: ?Sized] BorrowsMut<'a, T>, Self { marker: PhantomData });
+
+impl_marker_unsize!(Owns);
+impl_marker_unsize!(InvariantOwns);
+impl_marker_unsize!(Borrows<'a>);
+impl_marker_unsize!(BorrowsMut<'a>);
+
+impl_phantom_conversions!([T: ?Sized] Owns<T>, PhantomData<T>);
+impl_phantom_conversions!(['a, T: ?Sized] Borrows<'a, T>, PhantomData<&'a T>);
+impl_phantom_conversions!(['a, T: ?Sized] BorrowsMut<'a, T>, PhantomData<&'a mut T>);
+
+impl_marker_traits!([T: ?Sized] Owns<T>, "Owns<{}>", type_name::<T>());
+impl_marker_traits!([T: ?Sized] InvariantOwns<T>, "InvariantOwns<{}>", type_name::<T>());
+impl_marker_traits!(['a, T: ?Sized] Borrows<'a, T>, "Borrows<'_, {}>", type_name::<T>());
+impl_marker_traits!(['a, T: ?Sized] BorrowsMut<'a, T>, "BorrowsMut<'_, {}>", type_name::<T>());
+
+impl<T: ?Sized> private::Sealed for Owns<T> {}
+impl<T: ?Sized> private::Sealed for InvariantOwns<T> {}
+impl<T: ?Sized> private::Sealed for Borrows<'_, T> {}
+impl<T: ?Sized> private::Sealed for BorrowsMut<'_, T> {}
+
+// NOTE: The borrowing markers cannot keep their lifetime in `WithParam`,
+// since `U: 'a` is not known to hold, and no marker can own or borrow a `T`
+// contravariantly. These fall back to the plain markers, as documented on
+// `Variance`.
+impl<T: ?Sized> Variance for Owns<T> {
+    type Param = T;
+    type Flipped = Contravariant<T>;
+    type WithParam<U: ?Sized> = Owns<U>;
+    const KIND: VarianceKind = VarianceKind::Covariant;
+}
+impl<T: ?Sized> Variance for InvariantOwns<T> {
+    type Param = T;
+    type Flipped = Self;
+    type WithParam<U: ?Sized> = InvariantOwns<U>;
+    const KIND: VarianceKind = VarianceKind::Invariant;
+}
+impl<T: ?Sized> Variance for Borrows<'_, T> {
+    type Param = T;
+    type Flipped = Contravariant<T>;
+    type WithParam<U: ?Sized> = Covariant<U>;
+    const KIND: VarianceKind = VarianceKind::Covariant;
+}
+impl<T: ?Sized> Variance for BorrowsMut<'_, T> {
+    type Param = T;
+    type Flipped = Self;
+    type WithParam<U: ?Sized> = Invariant<U>;
+    const KIND: VarianceKind = VarianceKind::Invariant;
+}
@@ -0,0 +1,456 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use crate::Contravariant;
+
+/// A non-null raw pointer which is [invariant] with respect to `T`.
+///
+/// This is a drop-in replacement for [`NonNull<T>`], which is covariant and
+/// therefore easy to misuse in mutable data structures such as linked lists.
+/// Like `NonNull<T>`, `Option<InvariantNonNull<T>>` has the same size as
+/// `*mut T`.
+///
+/// [invariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+/// [`NonNull<T>`]: https://doc.rust-lang.org/stable/std/ptr/struct.NonNull.html
+///
+/// For example:
+/// ```
+/// use type_variance::InvariantNonNull;
+///
+/// struct Node<T> {
+///     value: T,
+///     next: Option<InvariantNonNull<Node<T>>>,
+/// }
+/// ```
+///
+/// With the `nightly` feature enabled, the pointer takes part in unsizing
+/// coercions like `NonNull<T>` does, e.g. from `InvariantNonNull<[u8; 4]>` to
+/// `InvariantNonNull<[u8]>`.
+// NOTE: The marker is a `PhantomData` rather than an `Invariant<T>`, since a
+// `CoerceUnsized` impl only allows `PhantomData` fields besides the pointer.
+pub struct InvariantNonNull<T: ?Sized> {
+    pointer: NonNull<T>,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+impl<T: ?Sized> InvariantNonNull<T> {
+    /// Creates a new `InvariantNonNull` if `ptr` is non-null.
+    pub fn new(ptr: *mut T) -> Option<Self> {
+        NonNull::new(ptr).map(Self::from)
+    }
+
+    /// Creates a new `InvariantNonNull`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null.
+    pub unsafe fn new_unchecked(ptr: *mut T) -> Self {
+        Self::from(NonNull::new_unchecked(ptr))
+    }
+
+    /// Acquires the underlying `*mut` pointer.
+    pub fn as_ptr(self) -> *mut T {
+        self.pointer.as_ptr()
+    }
+
+    /// Converts to the equivalent covariant `NonNull<T>`.
+    pub fn as_non_null(self) -> NonNull<T> {
+        self.pointer
+    }
+
+    /// Returns a shared reference to the value.
+    ///
+    /// # Safety
+    ///
+    /// This has the same requirements as [`NonNull::as_ref`].
+    ///
+    /// [`NonNull::as_ref`]: https://doc.rust-lang.org/stable/std/ptr/struct.NonNull.html#method.as_ref
+    pub unsafe fn as_ref<'a>(&self) -> &'a T {
+        &*self.pointer.as_ptr()
+    }
+
+    /// Returns a unique reference to the value.
+    ///
+    /// # Safety
+    ///
+    /// This has the same requirements as [`NonNull::as_mut`].
+    ///
+    /// [`NonNull::as_mut`]: https://doc.rust-lang.org/stable/std/ptr/struct.NonNull.html#method.as_mut
+    pub unsafe fn as_mut<'a>(&mut self) -> &'a mut T {
+        &mut *self.pointer.as_ptr()
+    }
+
+    /// Casts to a pointer of another type.
+    pub fn cast<U>(self) -> InvariantNonNull<U> {
+        InvariantNonNull::from(self.pointer.cast())
+    }
+}
+
+impl<T> InvariantNonNull<T> {
+    /// Creates a new `InvariantNonNull` that is dangling, but well-aligned.
+    pub fn dangling() -> Self {
+        Self::from(NonNull::dangling())
+    }
+}
+
+/// A non-null raw pointer which is [contravariant] with respect to `T`.
+///
+/// Since the pointer cannot mention `T` covariantly, it is stored without its
+/// type and so `T` must be sized. Like [`NonNull<T>`],
+/// `Option<ContravariantNonNull<T>>` has the same size as `*mut T`.
+///
+/// [contravariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+/// [`NonNull<T>`]: https://doc.rust-lang.org/stable/std/ptr/struct.NonNull.html
+///
+/// For example:
+/// ```
+/// use type_variance::ContravariantNonNull;
+///
+/// // A write-only slot, which may be used wherever a slot for a subtype of
+/// // `T` is expected.
+/// struct Slot<T> {
+///     target: ContravariantNonNull<T>,
+/// }
+/// ```
+pub struct ContravariantNonNull<T> {
+    pointer: NonNull<u8>,
+    _marker: Contravariant<T>,
+}
+
+impl<T> ContravariantNonNull<T> {
+    /// Creates a new `ContravariantNonNull` if `ptr` is non-null.
+    pub fn new(ptr: *mut T) -> Option<Self> {
+        NonNull::new(ptr).map(Self::from)
+    }
+
+    /// Creates a new `ContravariantNonNull`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null.
+    pub unsafe fn new_unchecked(ptr: *mut T) -> Self {
+        Self::from(NonNull::new_unchecked(ptr))
+    }
+
+    /// Creates a new `ContravariantNonNull` that is dangling, but
+    /// well-aligned.
+    pub fn dangling() -> Self {
+        Self::from(NonNull::dangling())
+    }
+
+    /// Acquires the underlying `*mut` pointer.
+    pub fn as_ptr(self) -> *mut T {
+        self.pointer.as_ptr().cast()
+    }
+
+    /// Converts to the equivalent covariant `NonNull<T>`.
+    pub fn as_non_null(self) -> NonNull<T> {
+        self.pointer.cast()
+    }
+
+    /// Returns a shared reference to the value.
+    ///
+    /// # Safety
+    ///
+    /// This has the same requirements as [`NonNull::as_ref`]. In addition, the
+    /// pointee must be a valid `T` at the current type of the pointer, and not
+    /// only at the type it was created with. Since the pointer is
+    /// contravariant, a `ContravariantNonNull<&'short str>` may have been
+    /// coerced to a `ContravariantNonNull<&'static str>`, which must not be
+    /// dereferenced while it points to a `&'short str`.
+    ///
+    /// [`NonNull::as_ref`]: https://doc.rust-lang.org/stable/std/ptr/struct.NonNull.html#method.as_ref
+    pub unsafe fn as_ref<'a>(&self) -> &'a T {
+        &*self.as_ptr()
+    }
+
+    /// Returns a unique reference to the value.
+    ///
+    /// # Safety
+    ///
+    /// This has the same requirements as [`NonNull::as_mut`]. In addition, the
+    /// pointee must be a valid `T` at the current type of the pointer, and not
+    /// only at the type it was created with. Since the pointer is
+    /// contravariant, a `ContravariantNonNull<&'short str>` may have been
+    /// coerced to a `ContravariantNonNull<&'static str>`, which must not be
+    /// dereferenced while it points to a `&'short str`.
+    ///
+    /// [`NonNull::as_mut`]: https://doc.rust-lang.org/stable/std/ptr/struct.NonNull.html#method.as_mut
+    pub unsafe fn as_mut<'a>(&mut self) -> &'a mut T {
+        &mut *self.as_ptr()
+    }
+
+    /// Casts to a pointer of another type.
+    pub fn cast<U>(self) -> ContravariantNonNull<U> {
+        ContravariantNonNull { pointer: self.pointer, _marker: Default::default(), }
+    }
+}
+
+// NOTE: These impls mirror those of `NonNull`, and are written by hand to
+// avoid the bounds on `T` that a #[derive] would add.
+macro_rules! impl_pointer_traits {
+    ($name:ident<$T:ident $(: ?$Sized:ident)?>) => {
+        impl<$T $(: ?$Sized)?> Clone for $name<$T> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<$T $(: ?$Sized)?> Copy for $name<$T> {}
+
+        impl<$T $(: ?$Sized)?> fmt::Debug for $name<$T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Pointer::fmt(&self.as_ptr(), f)
+            }
+        }
+
+        impl<$T $(: ?$Sized)?> fmt::Pointer for $name<$T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Pointer::fmt(&self.as_ptr(), f)
+            }
+        }
+
+        impl<$T $(: ?$Sized)?> PartialEq for $name<$T> {
+            fn eq(&self, other: &Self) -> bool {
+                core::ptr::eq(self.as_ptr(), other.as_ptr())
+            }
+        }
+
+        impl<$T $(: ?$Sized)?> Eq for $name<$T> {}
+
+        impl<$T $(: ?$Sized)?> Hash for $name<$T> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.as_non_null().hash(state)
+            }
+        }
+
+        impl<$T $(: ?$Sized)?> From<$name<$T>> for NonNull<$T> {
+            fn from(pointer: $name<$T>) -> Self {
+                pointer.as_non_null()
+            }
+        }
+
+        impl<$T $(: ?$Sized)?> From<&mut $T> for $name<$T> {
+            fn from(reference: &mut $T) -> Self {
+                Self::from(NonNull::from(reference))
+            }
+        }
+
+        impl<$T $(: ?$Sized)?> From<&$T> for $name<$T> {
+            fn from(reference: &$T) -> Self {
+                Self::from(NonNull::from(reference))
+            }
+        }
+    };
+}
+
+impl_pointer_traits!(InvariantNonNull<T: ?Sized>);
+impl_pointer_traits!(ContravariantNonNull<T>);
+
+impl<T: ?Sized> From<NonNull<T>> for InvariantNonNull<T> {
+    fn from(pointer: NonNull<T>) -> Self {
+        Self { pointer, _marker: PhantomData, }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T, U> core::ops::CoerceUnsized<InvariantNonNull<U>> for InvariantNonNull<T>
+where
+    T: ?Sized + core::marker::Unsize<U>,
+    U: ?Sized,
+{
+}
+
+#[cfg(feature = "nightly")]
+impl<T, U> core::ops::DispatchFromDyn<InvariantNonNull<U>> for InvariantNonNull<T>
+where
+    T: ?Sized + core::marker::Unsize<U>,
+    U: ?Sized,
+{
+}
+
+impl<T> From<NonNull<T>> for ContravariantNonNull<T> {
+    fn from(pointer: NonNull<T>) -> Self {
+        Self { pointer: pointer.cast(), _marker: Default::default(), }
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use self::boxed::InvariantBox;
+
+#[cfg(feature = "alloc")]
+mod boxed {
+    use alloc::boxed::Box;
+    use core::cmp::Ordering;
+    use core::fmt;
+    use core::hash::{Hash, Hasher};
+    use core::marker::PhantomData;
+    use core::ops::{Deref, DerefMut};
+
+    /// An owning pointer to a heap allocation which is [invariant] with
+    /// respect to `T`.
+    ///
+    /// This behaves like a [`Box<T>`], which is covariant, and dereferences
+    /// to the boxed value. It requires the `alloc` feature.
+    ///
+    /// [invariant]: https://en.wikipedia.org/wiki/Covariance_and_contravariance_(computer_science)
+    /// [`Box<T>`]: https://doc.rust-lang.org/stable/std/boxed/struct.Box.html
+    ///
+    /// For example:
+    /// ```
+    /// use std::cell::Cell;
+    /// use type_variance::InvariantBox;
+    ///
+    /// let boxed = InvariantBox::new(Cell::new(1));
+    /// boxed.set(2);
+    /// assert_eq!(InvariantBox::into_inner(boxed).get(), 2);
+    /// ```
+    ///
+    /// With the `nightly` feature enabled, the box takes part in unsizing
+    /// coercions like `Box<T>` does, e.g. from `InvariantBox<u8>` to
+    /// `InvariantBox<dyn Debug>`.
+    // NOTE: As for `InvariantNonNull`, the marker is a `PhantomData` so that
+    // the box may implement `CoerceUnsized`.
+    pub struct InvariantBox<T: ?Sized> {
+        inner: Box<T>,
+        marker: PhantomData<fn(T) -> T>,
+    }
+
+    impl<T> InvariantBox<T> {
+        /// Allocates memory on the heap and places `value` into it.
+        pub fn new(value: T) -> Self {
+            Self::from_box(Box::new(value))
+        }
+
+        /// Consumes the box, returning the wrapped value.
+        pub fn into_inner(this: Self) -> T {
+            *this.inner
+        }
+    }
+
+    impl<T: ?Sized> InvariantBox<T> {
+        /// Converts a `Box<T>` into an `InvariantBox<T>`.
+        pub fn from_box(inner: Box<T>) -> Self {
+            Self { inner, marker: PhantomData, }
+        }
+
+        /// Converts the box into the equivalent covariant `Box<T>`.
+        pub fn into_box(this: Self) -> Box<T> {
+            this.inner
+        }
+    }
+
+    #[cfg(feature = "nightly")]
+    impl<T, U> core::ops::CoerceUnsized<InvariantBox<U>> for InvariantBox<T>
+    where
+        T: ?Sized + core::marker::Unsize<U>,
+        U: ?Sized,
+    {
+    }
+
+    #[cfg(feature = "nightly")]
+    impl<T, U> core::ops::DispatchFromDyn<InvariantBox<U>> for InvariantBox<T>
+    where
+        T: ?Sized + core::marker::Unsize<U>,
+        U: ?Sized,
+    {
+    }
+
+    impl<T: ?Sized> Deref for InvariantBox<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.inner
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for InvariantBox<T> {
+        fn deref_mut(&mut self) -> &mut T {
+            &mut self.inner
+        }
+    }
+
+    impl<T: ?Sized> AsRef<T> for InvariantBox<T> {
+        fn as_ref(&self) -> &T {
+            &self.inner
+        }
+    }
+
+    impl<T: ?Sized> AsMut<T> for InvariantBox<T> {
+        fn as_mut(&mut self) -> &mut T {
+            &mut self.inner
+        }
+    }
+
+    // NOTE: These impls mirror those of `Box`, and are written by hand to
+    // allow unsized `T`, which a #[derive] would rule out.
+    impl<T: ?Sized> Clone for InvariantBox<T>
+    where
+        Box<T>: Clone,
+    {
+        fn clone(&self) -> Self {
+            Self::from_box(self.inner.clone())
+        }
+    }
+
+    impl<T: ?Sized> Default for InvariantBox<T>
+    where
+        Box<T>: Default,
+    {
+        fn default() -> Self {
+            Self::from_box(Box::default())
+        }
+    }
+
+    impl<T: ?Sized + PartialEq> PartialEq for InvariantBox<T> {
+        fn eq(&self, other: &Self) -> bool {
+            *self.inner == *other.inner
+        }
+    }
+
+    impl<T: ?Sized + Eq> Eq for InvariantBox<T> {}
+
+    impl<T: ?Sized + PartialOrd> PartialOrd for InvariantBox<T> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            (*self.inner).partial_cmp(&*other.inner)
+        }
+    }
+
+    impl<T: ?Sized + Ord> Ord for InvariantBox<T> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            (*self.inner).cmp(&*other.inner)
+        }
+    }
+
+    impl<T: ?Sized + Hash> Hash for InvariantBox<T> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            (*self.inner).hash(state)
+        }
+    }
+
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for InvariantBox<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&*self.inner, f)
+        }
+    }
+
+    impl<T: ?Sized + fmt::Display> fmt::Display for InvariantBox<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&*self.inner, f)
+        }
+    }
+
+    impl<T: ?Sized> From<Box<T>> for InvariantBox<T> {
+        fn from(inner: Box<T>) -> Self {
+            Self::from_box(inner)
+        }
+    }
+
+    impl<T> From<T> for InvariantBox<T> {
+        fn from(value: T) -> Self {
+            Self::new(value)
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{private, Contravariant, Covariant, Invariant, Variance, VarianceKind};
+
+/// The marker with the variance chosen by the tag `V`, for the parameter `T`.
+///
+/// This resolves to `Covariant<T>`, `Contravariant<T>`, or `Invariant<T>` for
+/// the tags [`Co`], [`Contra`], and [`Inv`] respectively, so that a building
+/// block can leave the choice of variance to its user.
+///
+/// [`Co`]: enum.Co.html
+/// [`Contra`]: enum.Contra.html
+/// [`Inv`]: enum.Inv.html
+///
+/// Note that the compiler computes variance once per type definition, and
+/// cannot see through `Phantom<V, T>` while `V` is still a parameter. A
+/// struct with a `Phantom<V, T>` field is therefore always _invariant_ with
+/// respect to `T`. To let the variance follow the tag, make the struct generic
+/// over the marker itself, and use `Phantom` where the tag is known:
+/// ```
+/// use type_variance::{Co, Inv, Phantom};
+///
+/// struct Handle<M> {
+///     raw: usize,
+///     marker: M,
+/// }
+///
+/// // Read-only handles are covariant, while mutable ones are invariant.
+/// type ReadHandle<T> = Handle<Phantom<Co, T>>;
+/// type WriteHandle<T> = Handle<Phantom<Inv, T>>;
+///
+/// fn shorten<'a>(handle: ReadHandle<&'static str>) -> ReadHandle<&'a str> {
+///     handle
+/// }
+/// ```
+pub type Phantom<V, T> = <V as VarianceTag>::Marker<T>;
+
+/// A sealed trait implemented by the tag types [`Co`], [`Contra`], and
+/// [`Inv`], which name a variance at the type level.
+///
+/// [`Co`]: enum.Co.html
+/// [`Contra`]: enum.Contra.html
+/// [`Inv`]: enum.Inv.html
+pub trait VarianceTag: private::Sealed {
+    /// The marker with this variance for the parameter `T`.
+    type Marker<T: ?Sized>: Variance;
+
+    /// The variance named by this tag.
+    const KIND: VarianceKind;
+}
+
+/// Tag type naming covariance, for use with [`Phantom`].
+///
+/// [`Phantom`]: type.Phantom.html
+#[derive(Clone, Copy, Debug)]
+pub enum Co {}
+
+/// Tag type naming contravariance, for use with [`Phantom`].
+///
+/// [`Phantom`]: type.Phantom.html
+#[derive(Clone, Copy, Debug)]
+pub enum Contra {}
+
+/// Tag type naming invariance, for use with [`Phantom`].
+///
+/// [`Phantom`]: type.Phantom.html
+#[derive(Clone, Copy, Debug)]
+pub enum Inv {}
+
+impl private::Sealed for Co {}
+impl private::Sealed for Contra {}
+impl private::Sealed for Inv {}
+
+impl VarianceTag for Co {
+    type Marker<T: ?Sized> = Covariant<T>;
+    const KIND: VarianceKind = VarianceKind::Covariant;
+}
+impl VarianceTag for Contra {
+    type Marker<T: ?Sized> = Contravariant<T>;
+    const KIND: VarianceKind = VarianceKind::Contravariant;
+}
+impl VarianceTag for Inv {
+    type Marker<T: ?Sized> = Invariant<T>;
+    const KIND: VarianceKind = VarianceKind::Invariant;
+}
@@ -0,0 +1,195 @@
+use crate::algebra::ops::Ops;
+use crate::{private, Variance, VarianceKind, VarianceTag};
+
+/// A marker type defined outside of this crate, which is trusted to have the
+/// variance named by its [`Tag`] with respect to its [`Param`].
+///
+/// [`Variance`] is sealed, so that generic code can rely on each marker being
+/// what it claims to be. This trait lets other crates add their own markers,
+/// such as an opaque handle for FFI, to the family: every implementor is also
+/// a [`Variance`] marker, whose flipped marker and markers for other
+/// parameters are the canonical [`Covariant`], [`Contravariant`], and
+/// [`Invariant`]. A marker with a lifetime parameter `'a` has the `Param`
+/// [`Lifetime<'a>`], as for the lifetime markers of this crate.
+///
+/// Prefer the [`impl_variance!`] macro, which implements this trait after
+/// checking the marker at compile time.
+///
+/// [`Tag`]: #associatedtype.Tag
+/// [`Param`]: #associatedtype.Param
+/// [`Variance`]: trait.Variance.html
+/// [`Covariant`]: enum.Covariant.html
+/// [`Contravariant`]: enum.Contravariant.html
+/// [`Invariant`]: enum.Invariant.html
+/// [`Lifetime<'a>`]: struct.Lifetime.html
+/// [`impl_variance!`]: macro.impl_variance.html
+///
+/// # Safety
+///
+/// The type must be zero-sized, and must have exactly the variance named by
+/// `Tag` with respect to `Param`. In particular, a marker declared as
+/// invariant must not be covariant or contravariant, since code may rely on
+/// the invariance of a marker for soundness.
+pub unsafe trait TrustedVariance: Default {
+    /// The parameter that the marker has a variance with respect to.
+    type Param: ?Sized;
+
+    /// The tag naming the variance of the marker, one of [`Co`], [`Contra`],
+    /// and [`Inv`].
+    ///
+    /// [`Co`]: enum.Co.html
+    /// [`Contra`]: enum.Contra.html
+    /// [`Inv`]: enum.Inv.html
+    type Tag: VarianceTag;
+}
+
+impl<M: TrustedVariance> private::Sealed for M {}
+
+impl<M> Variance for M
+where
+    M: TrustedVariance,
+    M::Tag: Ops,
+{
+    type Param = M::Param;
+    type Flipped = <<M::Tag as Ops>::Flip as VarianceTag>::Marker<M::Param>;
+    type WithParam<U: ?Sized> = <M::Tag as VarianceTag>::Marker<U>;
+    const KIND: VarianceKind = M::Tag::KIND;
+}
+
+/// Implements [`TrustedVariance`] for marker types with a single parameter,
+/// after checking their variance at compile time.
+///
+/// Each entry names the marker type with its parameter, followed by one of
+/// `covariant`, `contravariant`, or `invariant`. The parameter may be a type,
+/// optionally `?Sized`, or a lifetime. The macro asserts that the marker is
+/// zero-sized, and that a covariant or contravariant marker has the stated
+/// variance with the same coercions as [`assert_covariant!`] and
+/// [`assert_contravariant!`].
+///
+/// [`TrustedVariance`]: trait.TrustedVariance.html
+/// [`assert_covariant!`]: macro.assert_covariant.html
+/// [`assert_contravariant!`]: macro.assert_contravariant.html
+///
+/// For example:
+/// ```
+/// use std::marker::PhantomData;
+/// use type_variance::{impl_variance, variance, Variance, VarianceKind};
+///
+/// // A marker for the type behind an opaque FFI handle.
+/// struct Opaque<T: ?Sized>(PhantomData<fn() -> T>);
+///
+/// impl<T: ?Sized> Default for Opaque<T> {
+///     fn default() -> Self {
+///         Opaque(PhantomData)
+///     }
+/// }
+///
+/// impl_variance! {
+///     Opaque<T: ?Sized>: covariant;
+/// }
+///
+/// struct Handle<T: ?Sized> {
+///     raw: *mut u8,
+///     marker: Opaque<T>,
+/// }
+///
+/// let handle: Handle<str> = Handle {
+///     raw: std::ptr::null_mut(),
+///     marker: variance(),
+/// };
+/// assert_eq!(<Opaque<str> as Variance>::KIND, VarianceKind::Covariant);
+/// ```
+///
+/// Invariance is the absence of any coercion, and so cannot be checked in the
+/// same way. An `invariant` entry must therefore be prefixed with `unsafe`,
+/// which states that the caller has ensured the marker is neither covariant
+/// nor contravariant:
+/// ```
+/// # use std::cell::Cell;
+/// # use std::marker::PhantomData;
+/// # use type_variance::{impl_variance, Variance, VarianceKind};
+/// #
+/// struct Shared<T>(PhantomData<Cell<T>>);
+/// #
+/// # impl<T> Default for Shared<T> {
+/// #     fn default() -> Self {
+/// #         Shared(PhantomData)
+/// #     }
+/// # }
+///
+/// impl_variance! {
+///     // SAFETY: `Cell<T>` is invariant in `T`.
+///     unsafe Shared<T>: invariant;
+/// }
+/// # assert_eq!(<Shared<u8> as Variance>::KIND, VarianceKind::Invariant);
+/// ```
+#[macro_export]
+macro_rules! impl_variance {
+    () => {};
+    (unsafe $($rest:tt)*) => {
+        $crate::__impl_variance!(@entry [unsafe] $($rest)*);
+    };
+    ($($rest:tt)*) => {
+        $crate::__impl_variance!(@entry [] $($rest)*);
+    };
+}
+
+// Implements `TrustedVariance` for a single entry of `impl_variance!`.
+//
+// Arguments: kind [unsafe] name [type with placeholder] [instance] [generics] [type] [param]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_variance {
+    (@entry $unsafe:tt $name:ident<$lt:lifetime>: $kind:ident; $($rest:tt)*) => {
+        $crate::__impl_variance!(
+            $kind $unsafe $name [$name<'_>] [$name<'static>] [$lt] [$name<$lt>]
+            [$crate::Lifetime<$lt>]
+        );
+        $crate::impl_variance!($($rest)*);
+    };
+    (@entry $unsafe:tt $name:ident<$param:ident $(: ?$sized:ident)?>: $kind:ident; $($rest:tt)*) => {
+        $crate::__impl_variance!(
+            $kind $unsafe $name [$name<_>] [$name<&'static ()>] [$param $(: ?$sized)?]
+            [$name<$param>] [$param]
+        );
+        $crate::impl_variance!($($rest)*);
+    };
+    (covariant $unsafe:tt $name:ident [$($check:tt)*] $instance:tt $generics:tt $ty:tt $param:tt) => {
+        $crate::assert_covariant!($($check)*);
+        $crate::__impl_variance!(@impl Co $name $instance $generics $ty $param);
+    };
+    (contravariant $unsafe:tt $name:ident [$($check:tt)*] $instance:tt $generics:tt $ty:tt $param:tt) => {
+        $crate::assert_contravariant!($($check)*);
+        $crate::__impl_variance!(@impl Contra $name $instance $generics $ty $param);
+    };
+    (invariant [unsafe] $name:ident $check:tt $instance:tt $generics:tt $ty:tt $param:tt) => {
+        $crate::__impl_variance!(@impl Inv $name $instance $generics $ty $param);
+    };
+    (invariant [] $name:ident $check:tt $instance:tt $generics:tt $ty:tt $param:tt) => {
+        compile_error!(concat!(
+            "the invariance of `",
+            stringify!($name),
+            "` cannot be checked, and must be stated with `unsafe ",
+            stringify!($name),
+            "<..>: invariant;`",
+        ));
+    };
+    ($kind:ident $unsafe:tt $name:ident $check:tt $instance:tt $generics:tt $ty:tt $param:tt) => {
+        compile_error!(concat!(
+            "expected one of `covariant`, `contravariant`, or `invariant`, found `",
+            stringify!($kind),
+            "`",
+        ));
+    };
+    (@impl $tag:ident $name:ident [$instance:ty] [$($generics:tt)*] [$ty:ty] [$param:ty]) => {
+        const _: () = assert!(
+            ::core::mem::size_of::<$instance>() == 0,
+            concat!("`", stringify!($name), "` is not zero-sized"),
+        );
+
+        unsafe impl<$($generics)*> $crate::TrustedVariance for $ty {
+            type Param = $param;
+            type Tag = $crate::$tag;
+        }
+    };
+}
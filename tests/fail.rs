@@ -5,4 +5,10 @@ fn failure_tests() {
     t.compile_fail("tests/fail_contravariant.rs");
     t.compile_fail("tests/fail_invariant_covariant.rs");
     t.compile_fail("tests/fail_invariant_contravariant.rs");
+    t.compile_fail("tests/fail_assert_covariant.rs");
+    t.compile_fail("tests/fail_auto_traits.rs");
+    t.compile_fail("tests/fail_ownership.rs");
+    t.compile_fail("tests/fail_phantom.rs");
+    t.compile_fail("tests/fail_higher_ranked.rs");
+    t.compile_fail("tests/fail_impl_variance.rs");
 }
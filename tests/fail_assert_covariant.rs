@@ -0,0 +1,7 @@
+use type_variance::{assert_covariant, Contravariant, Invariant};
+
+assert_covariant!(Contravariant<_>);
+assert_covariant!(Invariant<_>);
+assert_covariant!(Invariant<u8>);
+
+fn main() {}
@@ -0,0 +1,14 @@
+use std::panic::UnwindSafe;
+use type_variance::{NotSend, NotSync, NotUnpin, NotUnwindSafe};
+
+fn send<T: Send>() {}
+fn sync<T: Sync>() {}
+fn unpin<T: Unpin>() {}
+fn unwind_safe<T: UnwindSafe>() {}
+
+fn main() {
+    send::<NotSend>();
+    sync::<NotSync>();
+    unpin::<NotUnpin>();
+    unwind_safe::<NotUnwindSafe>();
+}
@@ -0,0 +1,8 @@
+use type_variance::Covariant;
+
+// A callback for one lifetime cannot stand in for one for every lifetime.
+fn generalize(marker: Covariant<fn(&'static u8)>) -> Covariant<for<'a> fn(&'a u8)> {
+    marker
+}
+
+fn main() {}
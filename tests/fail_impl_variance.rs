@@ -0,0 +1,37 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+use type_variance::impl_variance;
+
+// Invariant, since it holds a `Cell<T>`.
+struct Shared<T>(PhantomData<Cell<T>>);
+
+impl<T> Default for Shared<T> {
+    fn default() -> Self {
+        Shared(PhantomData)
+    }
+}
+
+// Covariant, but not zero-sized.
+#[derive(Default)]
+struct Tagged<'a>(u8, PhantomData<&'a ()>);
+
+impl_variance! {
+    Shared<T>: covariant;
+    Tagged<'a>: covariant;
+}
+
+// Invariance cannot be checked, and so must be stated with `unsafe`.
+struct Opaque<T>(PhantomData<fn(T) -> T>);
+
+impl<T> Default for Opaque<T> {
+    fn default() -> Self {
+        Opaque(PhantomData)
+    }
+}
+
+impl_variance! {
+    Opaque<T>: invariant;
+}
+
+fn main() {}
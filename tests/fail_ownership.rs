@@ -0,0 +1,13 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use type_variance::{Borrows, BorrowsMut, InvariantOwns, Owns};
+
+fn send<T: Send>() {}
+fn sync<T: Sync>() {}
+
+fn main() {
+    send::<Owns<Rc<()>>>();
+    sync::<InvariantOwns<Cell<()>>>();
+    send::<Borrows<'static, Cell<()>>>();
+    send::<BorrowsMut<'static, Rc<()>>>();
+}
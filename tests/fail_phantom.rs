@@ -0,0 +1,12 @@
+use type_variance::{Co, Phantom, VarianceTag};
+
+// The variance of `T` cannot follow a tag which is a parameter.
+struct Handle<V: VarianceTag, T> {
+    marker: Phantom<V, T>,
+}
+
+fn shorten<'a>(handle: Handle<Co, &'static str>) -> Handle<Co, &'a str> {
+    handle
+}
+
+fn main() {}
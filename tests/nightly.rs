@@ -0,0 +1,7 @@
+#[test]
+fn nightly_tests() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/nightly/pass_unsize.rs");
+    t.compile_fail("tests/nightly/fail_coerce_marker.rs");
+    t.compile_fail("tests/nightly/fail_drop_check.rs");
+}
@@ -0,0 +1,18 @@
+use type_variance::Covariant;
+
+// The markers themselves are not pointers, and do not coerce implicitly.
+fn unsize(marker: Covariant<[u8; 4]>) -> Covariant<[u8]> {
+    marker
+}
+
+struct Handle<T: ?Sized> {
+    raw: *const u8,
+    marker: Covariant<T>,
+}
+
+// Nor does a struct whose only use of `T` is a marker.
+fn unsize_handle(handle: Handle<[u8; 4]>) -> Handle<[u8]> {
+    handle
+}
+
+fn main() {}
@@ -0,0 +1,51 @@
+#![feature(dropck_eyepatch)]
+
+use std::cell::Cell;
+
+use type_variance::{Covariant, InvariantOwns, Owns};
+
+struct Reader<'a>(&'a Cell<u8>);
+
+impl Drop for Reader<'_> {
+    fn drop(&mut self) {
+        self.0.set(0);
+    }
+}
+
+// A collection which promises not to access its `T`s when dropped, other
+// than by dropping them, as far as its marker says it owns any.
+macro_rules! collection {
+    ($name:ident, $marker:ident) => {
+        struct $name<T> {
+            ptr: *mut T,
+            marker: $marker<T>,
+        }
+
+        impl<T> $name<T> {
+            fn new(_: &T) -> Self {
+                $name { ptr: std::ptr::null_mut(), marker: $marker::new() }
+            }
+        }
+
+        unsafe impl<#[may_dangle] T> Drop for $name<T> {
+            fn drop(&mut self) {}
+        }
+    };
+}
+
+collection!(CovariantVec, Covariant);
+collection!(OwnsVec, Owns);
+collection!(InvariantOwnsVec, InvariantOwns);
+
+fn main() {
+    // Accepted, since a `Covariant<T>` does not own a `T`.
+    let _covariant;
+    // Rejected, since the `Reader`s may be dropped after `cell`.
+    let _owns;
+    let _invariant_owns;
+    let (a, b, c) = (Cell::new(0), Cell::new(0), Cell::new(0));
+    let (a, b, c) = (Reader(&a), Reader(&b), Reader(&c));
+    _covariant = CovariantVec::new(&a);
+    _owns = OwnsVec::new(&b);
+    _invariant_owns = InvariantOwnsVec::new(&c);
+}
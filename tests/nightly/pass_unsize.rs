@@ -0,0 +1,36 @@
+#![feature(unsize)]
+
+use std::fmt::Debug;
+use std::marker::Unsize;
+use std::ptr::NonNull;
+
+use type_variance::{Covariant, InvariantBox, InvariantNonNull};
+
+// A handle which is covariant with respect to its target.
+struct Handle<T: ?Sized> {
+    raw: NonNull<u8>,
+    marker: Covariant<T>,
+}
+
+impl<T: ?Sized> Handle<T> {
+    fn unsize<U: ?Sized>(self) -> Handle<U>
+    where
+        T: Unsize<U>,
+    {
+        Handle { raw: self.raw, marker: self.marker.unsize() }
+    }
+}
+
+fn main() {
+    let mut array = [1u8, 2, 3, 4];
+
+    let pointer: InvariantNonNull<[u8; 4]> = InvariantNonNull::from(&mut array);
+    let slice: InvariantNonNull<[u8]> = pointer;
+    assert_eq!(slice.as_ptr().len(), 4);
+
+    let boxed: InvariantBox<dyn Debug> = InvariantBox::new(5u8);
+    assert_eq!(format!("{:?}", &*boxed), "5");
+
+    let handle: Handle<[u8; 4]> = Handle { raw: NonNull::from(&mut array).cast(), marker: Covariant::new() };
+    let _: Handle<[u8]> = handle.unsize();
+}